@@ -0,0 +1,131 @@
+//! Compares the breath first search on NodeId keys with the former
+//! implementation on "row-col" String keys, on a 1024x1024 grid.
+//!
+//! Run with `cargo bench`.
+
+use std::collections::vec_deque::VecDeque;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use rust_problem_search::breath_first_search::BreathFirstSearch;
+use rust_problem_search::graph::Graph;
+use rust_problem_search::node::Node;
+use rust_problem_search::position::Position;
+use rust_problem_search::problem::Problem;
+
+const SIZE: u32 = 1024;
+const RUNS: u32 = 5;
+
+#[derive(Clone)]
+struct StringNode {
+    position: Position,
+    neighbours: Vec<String>,
+}
+
+/// A grid of path tiles with a ground wall every eighth column,
+/// open at alternating ends so the search has to follow a long corridor
+fn build_graph() -> Graph {
+    let mut tiles = vec![1; (SIZE * SIZE) as usize];
+    for column in (4..SIZE).step_by(8) {
+        let gap = if (column / 8) % 2 == 0 { SIZE - 1 } else { 0 };
+        for row in 0..SIZE {
+            if row != gap {
+                tiles[(row * SIZE + column) as usize] = 2;
+            }
+        }
+    }
+    Graph::new(tiles, SIZE, SIZE).unwrap()
+}
+
+fn to_string_nodes(nodes: &[Node]) -> HashMap<String, StringNode> {
+    nodes
+        .iter()
+        .map(|node| {
+            let neighbours = node
+                .neighbours
+                .iter()
+                .map(|id| format!("{}", nodes[id.index()].position))
+                .collect();
+            (
+                format!("{}", node.position),
+                StringNode {
+                    position: node.position,
+                    neighbours,
+                },
+            )
+        })
+        .collect()
+}
+
+/// The breath first search as it was implemented on String keys
+fn string_search(
+    nodes: &HashMap<String, StringNode>,
+    start: &str,
+    goal: &str,
+) -> Option<HashMap<String, String>> {
+    let nodes = nodes.clone();
+    let mut frontier = VecDeque::new();
+    let mut frontier_hash = HashSet::new();
+    let mut explored = HashMap::new();
+
+    frontier.push_back((nodes[start].clone(), "".to_string()));
+
+    while let Some((node, parent_id)) = frontier.pop_front() {
+        explored.insert(format!("{}", node.position), parent_id.clone());
+        let parent = format!("{}", node.position);
+
+        for neighbour in node.neighbours.iter() {
+            if !explored.contains_key(neighbour) && !frontier_hash.contains(neighbour) {
+                if neighbour != goal {
+                    frontier_hash.insert(neighbour.clone());
+                    frontier.push_back((nodes[neighbour].clone(), parent.clone()));
+                } else {
+                    explored.insert(neighbour.clone(), parent.clone());
+                    return Some(explored);
+                }
+            }
+        }
+    }
+    None
+}
+
+fn measure<F: FnMut() -> bool>(mut run: F) -> Duration {
+    let mut total = Duration::new(0, 0);
+    for _ in 0..RUNS {
+        let now = Instant::now();
+        assert!(run());
+        total += now.elapsed();
+    }
+    total / RUNS
+}
+
+fn main() {
+    let graph = build_graph();
+    let start = Position::new(0, 0);
+    let goal = Position::new(SIZE - 1, SIZE - 1);
+
+    let problem = Problem::new(
+        graph.get_path_nodes(),
+        graph.get_node_id_at_position(start),
+        graph.get_node_id_at_position(goal),
+        graph.size,
+    )
+    .unwrap();
+    let string_nodes = to_string_nodes(problem.get_nodes());
+    let start_key = format!("{}", start);
+    let goal_key = format!("{}", goal);
+
+    let node_id = measure(|| BreathFirstSearch::search(&problem).unwrap().is_some());
+    let string = measure(|| string_search(&string_nodes, &start_key, &goal_key).is_some());
+
+    println!(
+        "breath first search on a {}x{} grid, mean of {} runs",
+        SIZE, SIZE, RUNS
+    );
+    println!("  String keys: {:>10.2?}", string);
+    println!("  NodeId keys: {:>10.2?}", node_id);
+    println!(
+        "  speed-up:    {:>10.1}x",
+        string.as_secs_f64() / node_id.as_secs_f64()
+    );
+}
@@ -0,0 +1,437 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::node::NodeId;
+use crate::problem::Problem;
+use crate::search_error::SearchError;
+use crate::search_problem::{SearchProblem, SearchTree};
+use crate::search_result::SearchResult;
+
+#[derive(Debug, Clone)]
+pub struct AStarSearch {}
+
+#[derive(Debug)]
+/// An entry in the frontier, ordered by the estimated total cost
+pub(crate) struct State {
+    pub(crate) estimate: f64,
+    pub(crate) cost: f64,
+    pub(crate) index: usize,
+}
+
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .total_cmp(&self.estimate)
+            .then_with(|| self.cost.total_cmp(&other.cost))
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+impl PartialEq for State {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for State {}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl AStarSearch {
+    /// Returns an Option with the cheapest path and the search statistics,
+    /// or an error if the problem is invalid
+    ///
+    /// The manhattan distance between a node and the goal is used as heuristic,
+    /// or the octile distance if the nodes have diagonal neighbours.
+    /// Entering a node costs the traversal cost of its tile, so on four-connected path nodes
+    /// the returned path has the same length as the one found by the breath first search.
+    ///
+    /// # Arguments
+    ///
+    /// * `problem` The problem contains the start, the end and the nodes where to find the path
+    ///
+    /// # Example
+    /// ```
+    /// use rust_problem_search::a_star_search::AStarSearch;
+    /// use rust_problem_search::problem::Problem;
+    /// use rust_problem_search::graph::Graph;
+    /// use rust_problem_search::position::Position;
+    ///
+    /// let tiles: Vec<u8> = vec![
+    ///     2, 1, 2,
+    ///     2, 1, 2,
+    ///     2, 1, 2];
+    ///
+    /// let graph = Graph::new(tiles, 3, 3).unwrap();
+    ///
+    /// let problem = Problem::new(
+    ///     graph.get_path_nodes(),
+    ///     graph.get_node_id_at_position(Position::new(0, 1)),
+    ///     graph.get_node_id_at_position(Position::new(2, 1)),
+    ///     graph.size,
+    /// ).unwrap();
+    ///
+    /// let result = AStarSearch::search(&problem).unwrap();
+    /// ```
+    pub fn search(problem: &Problem) -> Result<Option<SearchResult>, SearchError> {
+        problem.validate()?;
+
+        let heuristic = AStarSearch::get_heuristic(problem);
+        Ok(
+            AStarSearch::search_with_estimate(problem, |id, cost| cost + heuristic(id))
+                .map(|result| result.map(|id| problem.get_position(id))),
+        )
+    }
+
+    /// Returns an Option with the cheapest path of states to a goal and the search statistics,
+    /// guided by the heuristic of the problem
+    ///
+    /// # Arguments
+    ///
+    /// * `problem` Any problem with an initial state, a goal test and successors
+    pub fn search_problem<P: SearchProblem>(problem: &P) -> Option<SearchResult<P::State>> {
+        AStarSearch::search_with_estimate(problem, |state, cost| cost + problem.heuristic(state))
+    }
+
+    /// Returns the manhattan distance between a node and the goal,
+    /// or the octile distance if the nodes have diagonal neighbours
+    pub(crate) fn get_heuristic(problem: &Problem) -> impl Fn(&NodeId) -> f64 + '_ {
+        move |id| problem.get_goal_distance(*id)
+    }
+
+    /// Runs a best first search which expands the state with the lowest estimate first,
+    /// the estimate is computed from a state and the cost to reach it
+    ///
+    /// The path is the cheapest one if the estimate is the cost plus a heuristic
+    /// which never overestimates the cost between a state and the goal.
+    pub(crate) fn search_with_estimate<P, E>(
+        problem: &P,
+        estimate: E,
+    ) -> Option<SearchResult<P::State>>
+    where
+        P: SearchProblem,
+        E: Fn(&P::State, f64) -> f64,
+    {
+        let mut tree = SearchTree::new(problem);
+        let mut closed = vec![false];
+        let mut frontier = BinaryHeap::new();
+        let mut expanded = 0;
+
+        if problem.is_goal(tree.state(0)) {
+            return Some(SearchResult::from_tree(&tree, 0, expanded, 0));
+        }
+
+        frontier.push(State {
+            estimate: estimate(tree.state(0), 0.0),
+            cost: 0.0,
+            index: 0,
+        });
+        let mut frontier_peak = frontier.len();
+
+        while let Some(State { cost, index, .. }) = frontier.pop() {
+            if closed[index] || cost > tree.cost(index) {
+                continue;
+            }
+            closed[index] = true;
+
+            if problem.is_goal(tree.state(index)) {
+                return Some(SearchResult::from_tree(
+                    &tree,
+                    index,
+                    expanded,
+                    frontier_peak,
+                ));
+            }
+            expanded += 1;
+
+            for (state, step_cost) in problem.successors(tree.state(index)) {
+                let next_cost = cost + step_cost;
+                let next = match tree.get(&state) {
+                    Some(next) if closed[next] || next_cost >= tree.cost(next) => continue,
+                    Some(next) => {
+                        tree.update(next, index, next_cost);
+                        next
+                    }
+                    None => {
+                        closed.push(false);
+                        tree.insert(state, Some(index), next_cost)
+                    }
+                };
+                frontier.push(State {
+                    estimate: estimate(tree.state(next), next_cost),
+                    cost: next_cost,
+                    index: next,
+                });
+                frontier_peak = frontier_peak.max(frontier.len());
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::SQRT_2;
+
+    use crate::breath_first_search::BreathFirstSearch;
+    use crate::connectivity::Connectivity;
+    use crate::graph::Graph;
+    use crate::position::Position;
+    use crate::uniform_cost_search::UniformCostSearch;
+
+    use super::*;
+
+    #[test]
+    fn search_with_valid_path_returns_result() {
+        let tiles: Vec<u8> = vec![
+            2, 1, 2,
+            2, 1, 2,
+            2, 1, 2];
+        let graph = Graph::new(tiles, 3, 3).unwrap();
+        let problem = Problem::new(
+            graph.get_path_nodes(),
+            graph.get_node_id_at_position(Position::new(0, 1)),
+            graph.get_node_id_at_position(Position::new(2, 1)),
+            graph.size,
+        )
+        .unwrap();
+        let result = AStarSearch::search(&problem).unwrap();
+        let unwrap_result = result.unwrap();
+
+        assert_eq!(
+            vec![
+                Position::new(0, 1),
+                Position::new(1, 1),
+                Position::new(2, 1),
+            ],
+            unwrap_result.path
+        );
+        assert_eq!(2.0, unwrap_result.cost);
+    }
+
+    #[test]
+    fn search_with_start_equals_goal_returns_result() {
+        let tiles: Vec<u8> = vec![
+            2, 1, 2,
+            2, 1, 2,
+            2, 1, 2];
+        let graph = Graph::new(tiles, 3, 3).unwrap();
+        let problem = Problem::new(
+            graph.get_path_nodes(),
+            graph.get_node_id_at_position(Position::new(0, 1)),
+            graph.get_node_id_at_position(Position::new(0, 1)),
+            graph.size,
+        )
+        .unwrap();
+        let result = AStarSearch::search(&problem).unwrap();
+        let unwrap_result = result.unwrap();
+
+        assert_eq!(vec![Position::new(0, 1)], unwrap_result.path);
+    }
+
+    #[test]
+    fn search_without_path_returns_none() {
+        let tiles: Vec<u8> = vec![
+            2, 1, 2,
+            2, 2, 2,
+            2, 1, 2];
+        let graph = Graph::new(tiles, 3, 3).unwrap();
+        let problem = Problem::new(
+            graph.get_path_nodes(),
+            graph.get_node_id_at_position(Position::new(0, 1)),
+            graph.get_node_id_at_position(Position::new(2, 1)),
+            graph.size,
+        )
+        .unwrap();
+
+        assert_eq!(None, AStarSearch::search(&problem).unwrap());
+    }
+
+    #[test]
+    fn search_with_unknown_goal_returns_error() {
+        let tiles: Vec<u8> = vec![
+            2, 1, 2,
+            2, 1, 2,
+            2, 1, 2];
+        let graph = Graph::new(tiles, 3, 3).unwrap();
+        let mut problem = Problem::new(
+            graph.get_path_nodes(),
+            graph.get_node_id_at_position(Position::new(0, 1)),
+            graph.get_node_id_at_position(Position::new(2, 1)),
+            graph.size,
+        )
+        .unwrap();
+        problem.goal = NodeId(9);
+
+        assert_eq!(
+            Err(SearchError::UnknownGoal(NodeId(9))),
+            AStarSearch::search(&problem)
+        );
+    }
+
+    #[test]
+    fn search_returns_path_with_same_length_as_breath_first_search() {
+        let tiles: Vec<u8> = vec![
+            1, 1, 1, 1, 1, 1,
+            1, 2, 2, 2, 2, 1,
+            1, 1, 1, 1, 2, 1,
+            2, 2, 2, 1, 2, 1,
+            1, 1, 1, 1, 2, 1,
+            1, 2, 2, 2, 2, 1,
+        ];
+        let graph = Graph::new(tiles, 6, 6).unwrap();
+        let problem = Problem::new(
+            graph.get_path_nodes(),
+            graph.get_node_id_at_position(Position::new(2, 0)),
+            graph.get_node_id_at_position(Position::new(5, 0)),
+            graph.size,
+        )
+        .unwrap();
+        let a_star_result = AStarSearch::search(&problem).unwrap().unwrap();
+        let breath_first_result = BreathFirstSearch::search(&problem).unwrap().unwrap();
+
+        assert_eq!(9, a_star_result.length);
+        assert_eq!(breath_first_result.length, a_star_result.length);
+    }
+
+    #[test]
+    fn search_with_possible_path_returns_path_with_same_length_as_breath_first_search() {
+        let tiles: Vec<u8> = vec![
+            2, 2, 2, 0, 0, 0,
+            2, 1, 1, 0, 0, 0,
+            2, 1, 2, 0, 0, 0,
+            0, 0, 0, 2, 2, 2,
+            0, 0, 0, 1, 1, 2,
+            0, 0, 0, 2, 2, 2,
+        ];
+        let graph = Graph::new(tiles, 6, 6).unwrap();
+        let problem = Problem::new(
+            graph.get_possible_nodes(),
+            graph.get_node_id_at_position(Position::new(1, 1)),
+            graph.get_node_id_at_position(Position::new(4, 4)),
+            graph.size,
+        )
+        .unwrap();
+        let a_star_result = AStarSearch::search(&problem).unwrap().unwrap();
+        let breath_first_result = BreathFirstSearch::search(&problem).unwrap().unwrap();
+
+        assert_eq!(breath_first_result.length, a_star_result.length);
+    }
+
+    #[test]
+    fn search_on_open_grid_expands_fewer_nodes_than_breath_first_search() {
+        let graph = Graph::new(vec![1; 400], 20, 20).unwrap();
+        let problem = Problem::new(
+            graph.get_path_nodes(),
+            graph.get_node_id_at_position(Position::new(0, 0)),
+            graph.get_node_id_at_position(Position::new(19, 19)),
+            graph.size,
+        )
+        .unwrap();
+        let a_star_result = AStarSearch::search(&problem).unwrap().unwrap();
+        let breath_first_result = BreathFirstSearch::search(&problem).unwrap().unwrap();
+
+        assert_eq!(38, a_star_result.length);
+        assert_eq!(breath_first_result.length, a_star_result.length);
+        assert!(a_star_result.expanded < breath_first_result.expanded);
+    }
+
+    #[test]
+    fn search_problem_expands_as_many_nodes_as_search() {
+        let graph = Graph::new(vec![1; 400], 20, 20).unwrap();
+        let problem = Problem::new(
+            graph.get_path_nodes(),
+            graph.get_node_id_at_position(Position::new(3, 17)),
+            graph.get_node_id_at_position(Position::new(16, 2)),
+            graph.size,
+        )
+        .unwrap();
+        let result = AStarSearch::search(&problem).unwrap().unwrap();
+        let problem_result = AStarSearch::search_problem(&problem).unwrap();
+
+        assert_eq!(result.expanded, problem_result.expanded);
+        assert_eq!(result.cost, problem_result.cost);
+    }
+
+    #[test]
+    fn search_with_eight_connectivity_returns_diagonal_path() {
+        let graph = Graph::new(vec![1; 25], 5, 5)
+            .unwrap()
+            .with_connectivity(Connectivity::Eight);
+        let problem = Problem::new(
+            graph.get_path_nodes(),
+            graph.get_node_id_at_position(Position::new(0, 0)),
+            graph.get_node_id_at_position(Position::new(4, 4)),
+            graph.size,
+        )
+        .unwrap();
+        let result = AStarSearch::search(&problem).unwrap().unwrap();
+
+        assert_eq!(4, result.length);
+        assert!((4.0 * SQRT_2 - result.cost).abs() < 1e-9);
+    }
+
+    #[test]
+    fn search_with_eight_connectivity_returns_same_cost_as_uniform_cost_search() {
+        let tiles: Vec<u8> = vec![
+            1, 1, 1, 1, 1, 1,
+            1, 2, 2, 2, 2, 1,
+            1, 0, 0, 1, 2, 1,
+            2, 2, 0, 1, 2, 1,
+            1, 1, 1, 1, 2, 0,
+            1, 2, 2, 2, 2, 1,
+        ];
+        let graph = Graph::new(tiles, 6, 6)
+            .unwrap()
+            .with_connectivity(Connectivity::EightWithoutCornerCutting);
+        let problem = Problem::new(
+            graph.get_possible_nodes(),
+            graph.get_node_id_at_position(Position::new(5, 0)),
+            graph.get_node_id_at_position(Position::new(5, 5)),
+            graph.size,
+        )
+        .unwrap();
+        let a_star_result = AStarSearch::search(&problem).unwrap().unwrap();
+        let uniform_cost_result = UniformCostSearch::search(&problem).unwrap().unwrap();
+
+        assert!((uniform_cost_result.cost - a_star_result.cost).abs() < 1e-9);
+    }
+
+    #[test]
+    fn get_path_returns_result_as_vector() {
+        let tiles: Vec<u8> = vec![
+            2, 2, 2, 2, 2, 2,
+            2, 1, 2, 2, 1, 2,
+            2, 1, 2, 2, 1, 2,
+            2, 1, 1, 1, 1, 2,
+            2, 1, 2, 2, 1, 2,
+            2, 1, 2, 2, 2, 2,
+        ];
+        let graph = Graph::new(tiles, 6, 6).unwrap();
+        let problem = Problem::new(
+            graph.get_path_nodes(),
+            graph.get_node_id_at_position(Position::new(1, 1)),
+            graph.get_node_id_at_position(Position::new(1, 4)),
+            graph.size,
+        )
+        .unwrap();
+        let result = AStarSearch::search(&problem).unwrap().unwrap();
+
+        assert_eq!(
+            vec![
+                0, 0, 0, 0, 0, 0,
+                0, 1, 0, 0, 1, 0,
+                0, 1, 0, 0, 1, 0,
+                0, 1, 1, 1, 1, 0,
+                0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0,
+            ],
+            BreathFirstSearch::get_path(&result, &graph)
+        );
+    }
+}
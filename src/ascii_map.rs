@@ -0,0 +1,276 @@
+use crate::graph::Graph;
+use crate::position::Position;
+use crate::problem::Problem;
+use crate::search_error::SearchError;
+use crate::tile::Tile;
+
+#[derive(Debug, Clone)]
+/// A graph read from an ascii map together with its start and goal markers
+pub struct AsciiMap {
+    pub graph: Graph,
+    pub start: Option<Position>,
+    pub goal: Option<Position>,
+}
+
+impl AsciiMap {
+    /// Returns a problem from the start to the goal marker
+    /// on the nodes of `Graph::get_path_nodes`
+    pub fn get_path_problem(&self) -> Result<Problem, SearchError> {
+        let (start, goal) = self.get_markers()?;
+        Problem::new(
+            self.graph.get_path_nodes(),
+            self.graph.get_node_id_at_position(start),
+            self.graph.get_node_id_at_position(goal),
+            self.graph.size,
+        )
+    }
+
+    /// Returns a problem from the start to the goal marker
+    /// on the nodes of `Graph::get_possible_nodes`
+    pub fn get_possible_problem(&self) -> Result<Problem, SearchError> {
+        let (start, goal) = self.get_markers()?;
+        Problem::new(
+            self.graph.get_possible_nodes(),
+            self.graph.get_node_id_at_position(start),
+            self.graph.get_node_id_at_position(goal),
+            self.graph.size,
+        )
+    }
+
+    fn get_markers(&self) -> Result<(Position, Position), SearchError> {
+        let start = self.start.ok_or(SearchError::MissingStart)?;
+        let goal = self.goal.ok_or(SearchError::MissingGoal)?;
+        Ok((start, goal))
+    }
+}
+
+impl Graph {
+    /// Returns a graph read from an ascii map
+    ///
+    /// Each line is a row, `.` is a path, `#` a ground and ` ` an empty tile.
+    /// `S` and `G` mark the start and the goal on a path tile.
+    /// A leading empty line is ignored, so maps can start on their own line.
+    ///
+    /// # Arguments
+    ///
+    /// * `map` - The ascii map
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_problem_search::breath_first_search::BreathFirstSearch;
+    /// use rust_problem_search::graph::Graph;
+    ///
+    /// let map = "#####\n#S..#\n###G#\n";
+    ///
+    /// let ascii_map = Graph::from_ascii(map).unwrap();
+    /// let problem = ascii_map.get_path_problem().unwrap();
+    /// let result = BreathFirstSearch::search(&problem).unwrap().unwrap();
+    ///
+    /// assert_eq!(3, result.length);
+    /// ```
+    pub fn from_ascii(map: &str) -> Result<AsciiMap, SearchError> {
+        let mut lines: Vec<&str> = map.lines().collect();
+        if lines.first().is_some_and(|line| line.is_empty()) {
+            lines.remove(0);
+        }
+
+        let width = lines.first().map_or(0, |line| line.chars().count());
+        let mut tiles = Vec::with_capacity(width * lines.len());
+        let mut start = None;
+        let mut goal = None;
+
+        for (row, line) in lines.iter().enumerate() {
+            let length = line.chars().count();
+            if length != width {
+                return Err(SearchError::InvalidRowLength {
+                    row: row as u32,
+                    expected: width,
+                    actual: length,
+                });
+            }
+            for (column, character) in line.chars().enumerate() {
+                let position = Position::new(row as u32, column as u32);
+                let tile = match character {
+                    'S' => {
+                        if start.is_some() {
+                            return Err(SearchError::DuplicateStart(position));
+                        }
+                        start = Some(position);
+                        Tile::Path
+                    }
+                    'G' => {
+                        if goal.is_some() {
+                            return Err(SearchError::DuplicateGoal(position));
+                        }
+                        goal = Some(position);
+                        Tile::Path
+                    }
+                    _ => Tile::from_char(character).ok_or(SearchError::InvalidCharacter {
+                        position,
+                        character,
+                    })?,
+                };
+                tiles.push(tile as u8);
+            }
+        }
+
+        let graph = Graph::new(tiles, width as u32, lines.len() as u32)?;
+        Ok(AsciiMap { graph, start, goal })
+    }
+
+    /// Returns the graph as ascii map with a path overlaid
+    ///
+    /// The path tiles are marked with `*`, its first tile with `S` and its last with `G`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The ordered positions of the path, may be empty
+    pub fn render_ascii(&self, path: &[Position]) -> String {
+        let mut characters: Vec<char> = self
+            .tiles
+            .iter()
+            .map(|value| Tile::from_u8(*value).to_char())
+            .collect();
+
+        for position in path.iter() {
+            characters[self.get_index_at_position(*position)] = '*';
+        }
+        if let Some(position) = path.first() {
+            characters[self.get_index_at_position(*position)] = 'S';
+        }
+        if let Some(position) = path.last().filter(|_| path.len() > 1) {
+            characters[self.get_index_at_position(*position)] = 'G';
+        }
+
+        let mut map = String::with_capacity(characters.len() + self.height as usize);
+        for row in characters.chunks(self.width.max(1) as usize) {
+            map.extend(row.iter());
+            map.push('\n');
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::breath_first_search::BreathFirstSearch;
+    use crate::uniform_cost_search::UniformCostSearch;
+
+    #[test]
+    fn from_ascii_returns_graph_and_markers() {
+        let ascii_map = Graph::from_ascii("#.  \nS.#G\n").unwrap();
+
+        assert_eq!(vec![2, 1, 0, 0, 1, 1, 2, 1], ascii_map.graph.tiles);
+        assert_eq!(4, ascii_map.graph.width);
+        assert_eq!(2, ascii_map.graph.height);
+        assert_eq!(Some(Position::new(1, 0)), ascii_map.start);
+        assert_eq!(Some(Position::new(1, 3)), ascii_map.goal);
+    }
+
+    #[test]
+    fn from_ascii_without_markers_returns_graph() {
+        let ascii_map = Graph::from_ascii("#.#\n#.#").unwrap();
+
+        assert_eq!(vec![2, 1, 2, 2, 1, 2], ascii_map.graph.tiles);
+        assert_eq!(None, ascii_map.start);
+        assert_eq!(None, ascii_map.goal);
+    }
+
+    #[test]
+    fn from_ascii_with_invalid_character_returns_error() {
+        assert_eq!(
+            Some(SearchError::InvalidCharacter {
+                position: Position::new(1, 2),
+                character: 'x'
+            }),
+            Graph::from_ascii("S..\n..x").err()
+        );
+    }
+
+    #[test]
+    fn from_ascii_with_short_row_returns_error() {
+        assert_eq!(
+            Some(SearchError::InvalidRowLength {
+                row: 1,
+                expected: 3,
+                actual: 2
+            }),
+            Graph::from_ascii("S..\n.G").err()
+        );
+    }
+
+    #[test]
+    fn from_ascii_with_second_start_returns_error() {
+        assert_eq!(
+            Some(SearchError::DuplicateStart(Position::new(1, 0))),
+            Graph::from_ascii("S.G\nS..").err()
+        );
+    }
+
+    #[test]
+    fn get_path_problem_without_goal_returns_error() {
+        let ascii_map = Graph::from_ascii("S..").unwrap();
+
+        assert_eq!(
+            Some(SearchError::MissingGoal),
+            ascii_map.get_path_problem().err()
+        );
+    }
+
+    #[test]
+    fn get_possible_problem_returns_problem_on_path_and_empty_tiles() {
+        let map = "
+#####
+#S# #
+#.  #
+#.#G#
+";
+        let ascii_map = Graph::from_ascii(map).unwrap();
+
+        assert_eq!(
+            None,
+            BreathFirstSearch::search(&ascii_map.get_path_problem().unwrap()).unwrap()
+        );
+        assert_eq!(
+            4,
+            BreathFirstSearch::search(&ascii_map.get_possible_problem().unwrap())
+                .unwrap()
+                .unwrap()
+                .length
+        );
+    }
+
+    #[test]
+    fn render_ascii_overlays_path() {
+        let map = "
+######
+#S...#
+#.##.#
+# ...#
+#.##G#
+";
+        let ascii_map = Graph::from_ascii(map).unwrap();
+        let problem = ascii_map.get_possible_problem().unwrap();
+        let result = UniformCostSearch::search(&problem).unwrap().unwrap();
+
+        assert_eq!(
+            "\
+######
+#S***#
+#.##*#
+# ..*#
+#.##G#
+",
+            ascii_map.graph.render_ascii(&result.path)
+        );
+    }
+
+    #[test]
+    fn render_ascii_without_path_returns_map() {
+        let ascii_map = Graph::from_ascii("#. \n.#.").unwrap();
+
+        assert_eq!("#. \n.#.\n", ascii_map.graph.render_ascii(&[]));
+    }
+}
@@ -0,0 +1,373 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Write;
+use std::fs;
+use std::process;
+
+use rust_problem_search::a_star_search::AStarSearch;
+use rust_problem_search::breath_first_search::BreathFirstSearch;
+use rust_problem_search::connectivity::Connectivity;
+use rust_problem_search::depth_first_search::DepthFirstSearch;
+use rust_problem_search::graph::Graph;
+use rust_problem_search::greedy_best_first_search::GreedyBestFirstSearch;
+use rust_problem_search::iterative_deepening_a_star_search::IterativeDeepeningAStarSearch;
+use rust_problem_search::iterative_deepening_search::IterativeDeepeningSearch;
+use rust_problem_search::jump_point_search::JumpPointSearch;
+use rust_problem_search::position::Position;
+use rust_problem_search::problem::Problem;
+use rust_problem_search::search_error::SearchError;
+use rust_problem_search::search_result::SearchResult;
+use rust_problem_search::uniform_cost_search::UniformCostSearch;
+use rust_problem_search::weighted_a_star_search::WeightedAStarSearch;
+
+const USAGE: &str = "\
+Usage: problem-search [OPTIONS] <MAP> [<START> <GOAL>]
+
+Searches a path on an ascii or Moving AI map and prints it.
+START and GOAL are positions as row-col, e.g. 3-12, and default
+to the S and G markers of an ascii map.
+
+Options:
+  -a, --algorithm <bfs|ucs|astar|dfs|iddfs|idastar|jps|greedy|weighted>
+                                    The search algorithm [default: astar]
+  -w, --weight <WEIGHT>             The heuristic weight of the weighted A* search [default: 2]
+  -n, --nodes <path|possible>       Walk on path tiles only, or on path and empty tiles [default: path]
+  -c, --connectivity <4|8|8-no-corner-cutting>
+                                    The neighbours of a tile [default: 4, 8-no-corner-cutting for Moving AI maps]
+  -r, --render                      Print the map with the path instead of the positions
+  -h, --help                        Print this help
+";
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Algorithm {
+    BreathFirst,
+    UniformCost,
+    AStar,
+    DepthFirst,
+    IterativeDeepening,
+    IterativeDeepeningAStar,
+    JumpPoint,
+    GreedyBestFirst,
+    WeightedAStar,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Nodes {
+    Path,
+    Possible,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Options {
+    map: String,
+    start: Option<Position>,
+    goal: Option<Position>,
+    algorithm: Algorithm,
+    nodes: Nodes,
+    connectivity: Option<Connectivity>,
+    weight: f64,
+    render: bool,
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        print!("{}", USAGE);
+        return;
+    }
+    let options = parse_args(&args).unwrap_or_else(|message| {
+        eprintln!("error: {}\n\n{}", message, USAGE);
+        process::exit(2);
+    });
+    match run(&options) {
+        Ok(Some(output)) => print!("{}", output),
+        Ok(None) => {
+            println!("no path found");
+            process::exit(1);
+        }
+        Err(error) => {
+            eprintln!("error: {}", error);
+            process::exit(2);
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut algorithm = Algorithm::AStar;
+    let mut nodes = Nodes::Path;
+    let mut connectivity = None;
+    let mut weight = 2.0;
+    let mut render = false;
+    let mut positionals = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "-a" | "--algorithm" => {
+                algorithm = match value()?.as_str() {
+                    "bfs" => Algorithm::BreathFirst,
+                    "ucs" | "dijkstra" => Algorithm::UniformCost,
+                    "astar" => Algorithm::AStar,
+                    "dfs" => Algorithm::DepthFirst,
+                    "iddfs" => Algorithm::IterativeDeepening,
+                    "idastar" => Algorithm::IterativeDeepeningAStar,
+                    "jps" => Algorithm::JumpPoint,
+                    "greedy" => Algorithm::GreedyBestFirst,
+                    "weighted" => Algorithm::WeightedAStar,
+                    other => return Err(format!("unknown algorithm {:?}", other)),
+                }
+            }
+            "-n" | "--nodes" => {
+                nodes = match value()?.as_str() {
+                    "path" => Nodes::Path,
+                    "possible" => Nodes::Possible,
+                    other => return Err(format!("unknown nodes {:?}", other)),
+                }
+            }
+            "-c" | "--connectivity" => {
+                connectivity = Some(match value()?.as_str() {
+                    "4" => Connectivity::Four,
+                    "8" => Connectivity::Eight,
+                    "8-no-corner-cutting" => Connectivity::EightWithoutCornerCutting,
+                    other => return Err(format!("unknown connectivity {:?}", other)),
+                })
+            }
+            "-w" | "--weight" => {
+                let value = value()?;
+                weight = value
+                    .parse()
+                    .map_err(|_| format!("invalid weight {:?}", value))?;
+            }
+            "-r" | "--render" => render = true,
+            other if other.starts_with('-') => return Err(format!("unknown option {}", other)),
+            other => positionals.push(other),
+        }
+    }
+
+    let (map, start, goal) = match positionals.as_slice() {
+        [map] => (map, None, None),
+        [map, start, goal] => (
+            map,
+            Some(
+                start
+                    .parse()
+                    .map_err(|error: SearchError| error.to_string())?,
+            ),
+            Some(
+                goal.parse()
+                    .map_err(|error: SearchError| error.to_string())?,
+            ),
+        ),
+        [] => return Err("missing map file".to_string()),
+        _ => return Err("expected a map file and optionally a start and a goal".to_string()),
+    };
+
+    Ok(Options {
+        map: map.to_string(),
+        start,
+        goal,
+        algorithm,
+        nodes,
+        connectivity,
+        weight,
+        render,
+    })
+}
+
+fn run(options: &Options) -> Result<Option<String>, Box<dyn Error>> {
+    let content = fs::read_to_string(&options.map)
+        .map_err(|error| format!("can not read {}: {}", options.map, error))?;
+    let (graph, start, goal) = load_map(&content, options)?;
+    let problem = get_problem(&graph, options.nodes, start, goal)?;
+
+    let (result, bound) = match options.algorithm {
+        Algorithm::BreathFirst => (BreathFirstSearch::search(&problem)?, None),
+        Algorithm::UniformCost => (UniformCostSearch::search(&problem)?, None),
+        Algorithm::AStar => (AStarSearch::search(&problem)?, None),
+        Algorithm::DepthFirst => (DepthFirstSearch::search(&problem)?, None),
+        Algorithm::IterativeDeepening => (IterativeDeepeningSearch::search(&problem)?, None),
+        Algorithm::IterativeDeepeningAStar => {
+            (IterativeDeepeningAStarSearch::search(&problem)?, None)
+        }
+        Algorithm::JumpPoint => (JumpPointSearch::search(&graph, &problem)?, None),
+        Algorithm::GreedyBestFirst => GreedyBestFirstSearch::search(&problem)?
+            .map_or((None, None), |bounded| {
+                (Some(bounded.result), Some(bounded.bound))
+            }),
+        Algorithm::WeightedAStar => WeightedAStarSearch::search(&problem, options.weight)?
+            .map_or((None, None), |bounded| {
+                (Some(bounded.result), Some(bounded.bound))
+            }),
+    };
+    Ok(result.map(|result| {
+        let mut output = format_result(&graph, &result, options.render);
+        if let Some(bound) = bound {
+            let _ = writeln!(output, "bound: {}", bound);
+        }
+        output
+    }))
+}
+
+/// Reads a Moving AI map if the content starts with its header, an ascii map otherwise
+fn load_map(content: &str, options: &Options) -> Result<(Graph, Position, Position), SearchError> {
+    let (mut graph, markers) = if content.starts_with("type ") {
+        (Graph::from_moving_ai(content)?, (None, None))
+    } else {
+        let ascii_map = Graph::from_ascii(content)?;
+        (ascii_map.graph, (ascii_map.start, ascii_map.goal))
+    };
+    if let Some(connectivity) = options.connectivity {
+        graph = graph.with_connectivity(connectivity);
+    }
+
+    let start = options
+        .start
+        .or(markers.0)
+        .ok_or(SearchError::MissingStart)?;
+    let goal = options.goal.or(markers.1).ok_or(SearchError::MissingGoal)?;
+    Ok((graph, start, goal))
+}
+
+fn get_problem(
+    graph: &Graph,
+    nodes: Nodes,
+    start: Position,
+    goal: Position,
+) -> Result<Problem, SearchError> {
+    for position in [start, goal].iter() {
+        if !graph.contains(*position) {
+            return Err(SearchError::PositionOutOfBounds(*position));
+        }
+    }
+    let nodes = match nodes {
+        Nodes::Path => graph.get_path_nodes(),
+        Nodes::Possible => graph.get_possible_nodes(),
+    };
+    Problem::new(
+        nodes,
+        graph.get_node_id_at_position(start),
+        graph.get_node_id_at_position(goal),
+        graph.size,
+    )
+}
+
+fn format_result(graph: &Graph, result: &SearchResult, render: bool) -> String {
+    let mut output = String::new();
+    if render {
+        output.push_str(&graph.render_ascii(&result.path));
+    } else {
+        let path: Vec<String> = result.path.iter().map(|p| p.to_string()).collect();
+        let _ = writeln!(output, "path: {}", path.join(" "));
+    }
+    let _ = writeln!(output, "length: {}", result.length);
+    let _ = writeln!(output, "cost: {}", result.cost);
+    let _ = writeln!(output, "expanded: {}", result.expanded);
+    let _ = writeln!(output, "frontier peak: {}", result.frontier_peak);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn options() -> Options {
+        parse_args(&args(&["map.txt"])).unwrap()
+    }
+
+    #[test]
+    fn parse_args_with_map_returns_defaults() {
+        assert_eq!(
+            Options {
+                map: "map.txt".to_string(),
+                start: None,
+                goal: None,
+                algorithm: Algorithm::AStar,
+                nodes: Nodes::Path,
+                connectivity: None,
+                weight: 2.0,
+                render: false,
+            },
+            options()
+        );
+    }
+
+    #[test]
+    fn parse_args_with_all_options_returns_options() {
+        let options = parse_args(&args(&[
+            "-a", "bfs", "map.txt", "--nodes", "possible", "1-2", "3-4", "-c", "8", "--render",
+            "-w", "1.5",
+        ]))
+        .unwrap();
+
+        assert_eq!(Algorithm::BreathFirst, options.algorithm);
+        assert_eq!(Nodes::Possible, options.nodes);
+        assert_eq!(Some(Connectivity::Eight), options.connectivity);
+        assert_eq!(1.5, options.weight);
+        assert_eq!(Some(Position::new(1, 2)), options.start);
+        assert_eq!(Some(Position::new(3, 4)), options.goal);
+        assert!(options.render);
+    }
+
+    #[test]
+    fn parse_args_with_invalid_arguments_returns_error() {
+        assert!(parse_args(&args(&[])).is_err());
+        assert!(parse_args(&args(&["map.txt", "1-2"])).is_err());
+        assert!(parse_args(&args(&["map.txt", "1-2", "x"])).is_err());
+        assert!(parse_args(&args(&["-a", "dfs2", "map.txt"])).is_err());
+        assert!(parse_args(&args(&["map.txt", "--nodes"])).is_err());
+    }
+
+    #[test]
+    fn load_map_uses_markers_of_ascii_map() {
+        let (graph, start, goal) = load_map("S.\n.G\n", &options()).unwrap();
+
+        assert_eq!(2, graph.width);
+        assert_eq!(Position::new(0, 0), start);
+        assert_eq!(Position::new(1, 1), goal);
+    }
+
+    #[test]
+    fn load_map_without_goal_returns_error() {
+        assert_eq!(
+            Some(SearchError::MissingGoal),
+            load_map("S.\n..\n", &options()).err()
+        );
+    }
+
+    #[test]
+    fn get_problem_with_position_outside_returns_error() {
+        let graph = Graph::from_ascii("..\n..\n").unwrap().graph;
+
+        assert_eq!(
+            Some(SearchError::PositionOutOfBounds(Position::new(2, 0))),
+            get_problem(
+                &graph,
+                Nodes::Path,
+                Position::new(0, 0),
+                Position::new(2, 0)
+            )
+            .err()
+        );
+    }
+
+    #[test]
+    fn format_result_renders_path_and_stats() {
+        let (graph, start, goal) = load_map("S. \n#.G\n", &options()).unwrap();
+        let problem = get_problem(&graph, Nodes::Path, start, goal).unwrap();
+        let result = BreathFirstSearch::search(&problem).unwrap().unwrap();
+
+        assert_eq!(
+            "path: 0-0 0-1 1-1 1-2\nlength: 3\ncost: 3\nexpanded: 3\nfrontier peak: 1\n",
+            format_result(&graph, &result, false)
+        );
+        assert!(format_result(&graph, &result, true).starts_with("S* \n#*G\n"));
+    }
+}
@@ -1,16 +1,79 @@
 use std::collections::vec_deque::VecDeque;
-use std::collections::{HashMap, HashSet};
 
 use crate::graph::Graph;
-use crate::node::Node;
-use crate::position::Position;
+use crate::node::NodeId;
 use crate::problem::Problem;
+use crate::search_error::SearchError;
+use crate::search_problem::{SearchProblem, SearchTree};
+use crate::search_result::SearchResult;
 
 #[derive(Debug, Clone)]
 pub struct BreathFirstSearch {}
 
+/// One direction of the bidirectional search
+struct Side {
+    layer: Vec<NodeId>,
+    depths: Vec<Option<u32>>,
+    links: Vec<Option<NodeId>>,
+}
+
+impl Side {
+    fn new(origin: NodeId, size: usize) -> Self {
+        let mut depths = vec![None; size];
+        depths[origin.index()] = Some(0);
+        Side {
+            layer: vec![origin],
+            depths,
+            links: vec![None; size],
+        }
+    }
+
+    /// Expands the whole current layer along the edges and returns
+    /// the cheapest node which is reached from both sides, with its distance
+    fn expand<E>(&mut self, other: &Side, edges: E, expanded: &mut usize) -> Option<(u32, NodeId)>
+    where
+        E: Fn(NodeId) -> Vec<NodeId>,
+    {
+        let mut meeting: Option<(u32, NodeId)> = None;
+        let mut next_layer = Vec::new();
+        for id in self.layer.iter() {
+            *expanded += 1;
+            let depth = self.depths[id.index()].unwrap_or(0) + 1;
+            for neighbour in edges(*id) {
+                if self.depths[neighbour.index()].is_none() {
+                    self.depths[neighbour.index()] = Some(depth);
+                    self.links[neighbour.index()] = Some(*id);
+                    next_layer.push(neighbour);
+                }
+                if let (Some(own), Some(other)) = (
+                    self.depths[neighbour.index()],
+                    other.depths[neighbour.index()],
+                ) {
+                    if meeting.is_none_or(|(distance, _)| own + other < distance) {
+                        meeting = Some((own + other, neighbour));
+                    }
+                }
+            }
+        }
+        self.layer = next_layer;
+        meeting
+    }
+
+    /// Returns the node ids from the node back to the origin of this side
+    fn chain(&self, id: NodeId) -> Vec<NodeId> {
+        let mut ids = vec![id];
+        let mut next = self.links[id.index()];
+        while let Some(link) = next {
+            ids.push(link);
+            next = self.links[link.index()];
+        }
+        ids
+    }
+}
+
 impl BreathFirstSearch {
-    /// Returns an Option a HashMap containing the result
+    /// Returns an Option with the shortest path in steps and the search statistics,
+    /// or an error if the problem is invalid
     ///
     /// # Arguments
     ///
@@ -21,73 +84,173 @@ impl BreathFirstSearch {
     /// use rust_problem_search::breath_first_search::BreathFirstSearch;
     /// use rust_problem_search::problem::Problem;
     /// use rust_problem_search::graph::Graph;
+    /// use rust_problem_search::position::Position;
     ///
     /// let tiles: Vec<u8> = vec![
     ///     2, 1, 2,
     ///     2, 1, 2,
     ///     2, 1, 2];
     ///
-    /// let graph = Graph::new(tiles, 3, 3);
+    /// let graph = Graph::new(tiles, 3, 3).unwrap();
     ///
     /// let problem = Problem::new(
     ///     graph.get_path_nodes(),
-    ///     "0-1".to_string(),
-    ///     "2-1".to_string(),
+    ///     graph.get_node_id_at_position(Position::new(0, 1)),
+    ///     graph.get_node_id_at_position(Position::new(2, 1)),
     ///     graph.size,
-    /// );
+    /// ).unwrap();
     ///
-    /// let result = BreathFirstSearch::search(&problem);
+    /// let result = BreathFirstSearch::search(&problem).unwrap();
     /// ```
-    pub fn search(problem: &Problem) -> Option<HashMap<String, String>> {
-        let nodes = problem.nodes.clone();
-        let start = &problem.start.clone();
-        let goal = &problem.goal.clone();
+    pub fn search(problem: &Problem) -> Result<Option<SearchResult>, SearchError> {
+        problem.validate()?;
+        Ok(BreathFirstSearch::search_problem(problem)
+            .map(|result| result.map(|id| problem.get_position(id))))
+    }
 
+    /// Returns an Option with the path of states with the fewest steps to a goal
+    /// and the search statistics
+    ///
+    /// # Arguments
+    ///
+    /// * `problem` Any problem with an initial state, a goal test and successors
+    pub fn search_problem<P: SearchProblem>(problem: &P) -> Option<SearchResult<P::State>> {
+        let mut tree = SearchTree::new(problem);
         let mut frontier = VecDeque::new();
-        let mut frontier_hash = HashSet::new();
-
-        let mut explored = HashMap::new();
-        let mut parent = "".to_string();
+        let mut expanded = 0;
+        let mut frontier_peak = 0;
 
-        if start == goal {
-            explored.insert(start.clone(), "".to_string());
-            return Some(explored);
+        if problem.is_goal(tree.state(0)) {
+            return Some(SearchResult::from_tree(&tree, 0, expanded, frontier_peak));
         }
 
-        frontier.push_back((nodes[start].clone(), parent.clone()));
+        frontier.push_back(0);
+        frontier_peak = frontier.len();
 
-        if let Some((n, _)) = frontier.get_mut(1) {
-            frontier_hash.insert(format!("{}", n.position.clone()));
-        }
+        while let Some(index) = frontier.pop_front() {
+            expanded += 1;
 
-        while let Some((node, parent_id)) = frontier.pop_front() {
-            explored.insert(format!("{}", node.position), parent_id.clone());
-            parent = format!("{}", node.position);
-
-            for neighbour in node.neighbours.iter() {
-                if !explored.contains_key(neighbour) && !frontier_hash.contains(neighbour) {
-                    if neighbour != goal {
-                        frontier_hash.insert(format!("{}", neighbour));
-                        frontier.push_back((nodes[neighbour].clone(), parent.clone()));
-                    } else {
-                        explored.insert(neighbour.clone(), parent.clone());
-                        frontier.clear();
-                        return Some(explored);
+            let cost = tree.cost(index);
+            for (state, step_cost) in problem.successors(tree.state(index)) {
+                if tree.get(&state).is_none() {
+                    let is_goal = problem.is_goal(&state);
+                    let next = tree.insert(state, Some(index), cost + step_cost);
+                    if is_goal {
+                        return Some(SearchResult::from_tree(
+                            &tree,
+                            next,
+                            expanded,
+                            frontier_peak,
+                        ));
                     }
+                    frontier.push_back(next);
+                    frontier_peak = frontier_peak.max(frontier.len());
                 }
             }
         }
         None
     }
 
-    /// Converts a flat vector from the solution found with the search function
+    /// Returns an Option with the shortest path in steps and the search statistics,
+    /// or an error if the problem is invalid
+    ///
+    /// One frontier grows from the start along the neighbours and one from the goal
+    /// against them, layer by layer on the smaller side, until they meet.
+    /// A neighbour without a link back is a one-way edge and only walked in its direction,
+    /// so the result is the same as the one of `search` and works with `get_path`.
     ///
+    /// # Arguments
+    ///
+    /// * `problem` The problem contains the start, the end and the nodes where to find the path
+    ///
+    /// # Example
+    /// ```
+    /// use rust_problem_search::breath_first_search::BreathFirstSearch;
+    /// use rust_problem_search::graph::Graph;
+    ///
+    /// let ascii_map = Graph::from_ascii("S....\n.###.\n....G\n").unwrap();
+    /// let problem = ascii_map.get_path_problem().unwrap();
+    ///
+    /// let result = BreathFirstSearch::search_bidirectional(&problem).unwrap().unwrap();
+    ///
+    /// assert_eq!(6, result.length);
+    /// ```
+    pub fn search_bidirectional(problem: &Problem) -> Result<Option<SearchResult>, SearchError> {
+        problem.validate()?;
+
+        let size = problem.get_nodes().len();
+        let mut predecessors = vec![Vec::new(); size];
+        for index in 0..size {
+            let id = NodeId::new(index);
+            for (neighbour, _) in problem.successors(&id) {
+                predecessors[neighbour.index()].push(id);
+            }
+        }
+
+        let mut forward = Side::new(problem.start, size);
+        let mut backward = Side::new(problem.goal, size);
+        let mut expanded = 0;
+        let mut frontier_peak = 0;
+        let mut meeting = if problem.start == problem.goal {
+            Some((0, problem.start))
+        } else {
+            None
+        };
+
+        while meeting.is_none() && !forward.layer.is_empty() && !backward.layer.is_empty() {
+            frontier_peak = frontier_peak.max(forward.layer.len() + backward.layer.len());
+            meeting = if forward.layer.len() <= backward.layer.len() {
+                forward.expand(
+                    &backward,
+                    |id| {
+                        problem
+                            .successors(&id)
+                            .into_iter()
+                            .map(|(id, _)| id)
+                            .collect()
+                    },
+                    &mut expanded,
+                )
+            } else {
+                backward.expand(
+                    &forward,
+                    |id| predecessors[id.index()].clone(),
+                    &mut expanded,
+                )
+            };
+        }
+
+        Ok(meeting.map(|(_, id)| {
+            let mut ids = forward.chain(id);
+            ids.reverse();
+            ids.extend(backward.chain(id).into_iter().skip(1));
+
+            let cost = ids
+                .windows(2)
+                .filter_map(|step| {
+                    problem.get_nodes()[step[1].index()]
+                        .cost_from(&problem.get_nodes()[step[0].index()])
+                })
+                .sum();
+            SearchResult {
+                length: ids.len() - 1,
+                path: ids.into_iter().map(|id| problem.get_position(id)).collect(),
+                cost,
+                expanded,
+                frontier_peak,
+            }
+        }))
+    }
+
+    /// Converts the path found with the search function to a flat vector,
+    /// where 1 marks a tile on the path
     ///
     /// # Example
     /// ```
     /// use rust_problem_search::breath_first_search::BreathFirstSearch;
     /// use rust_problem_search::problem::Problem;
     /// use rust_problem_search::graph::Graph;
+    /// use rust_problem_search::position::Position;
     ///
     /// let tiles: Vec<u8> = vec![
     ///     2, 2, 2, 2, 2, 2,
@@ -97,18 +260,19 @@ impl BreathFirstSearch {
     ///     2, 1, 2, 2, 1, 2,
     ///     2, 1, 2, 2, 2, 2,
     /// ];
-    /// let graph = Graph::new(tiles, 6, 6);
+    /// let graph = Graph::new(tiles, 6, 6).unwrap();
     ///
     /// let problem = Problem::new(
     ///     graph.get_path_nodes(),
-    ///     "1-1".to_string(),
-    ///     "1-4".to_string(),
+    ///     graph.get_node_id_at_position(Position::new(1, 1)),
+    ///     graph.get_node_id_at_position(Position::new(1, 4)),
     ///     graph.size,
-    /// );
-    /// let result = BreathFirstSearch::search(&problem);
+    /// ).unwrap();
+    /// let result = BreathFirstSearch::search(&problem).unwrap();
     ///
     /// let unwrap_result = result.unwrap();
     ///
+    /// assert_eq!(7, unwrap_result.length);
     /// assert_eq!(
     ///     vec![
     ///         0, 0, 0, 0, 0, 0,
@@ -118,64 +282,51 @@ impl BreathFirstSearch {
     ///         0, 0, 0, 0, 0, 0,
     ///         0, 0, 0, 0, 0, 0
     ///     ],
-    ///     BreathFirstSearch::get_path(&unwrap_result, &graph, &problem)
+    ///     BreathFirstSearch::get_path(&unwrap_result, &graph)
     /// );
     /// ```
-    pub fn get_path(result: &HashMap<String, String>, graph: &Graph, problem: &Problem) -> Vec<u8> {
+    pub fn get_path(result: &SearchResult, graph: &Graph) -> Vec<u8> {
         let mut tiles = vec![0; graph.size];
-        let nodes = problem.nodes.clone();
-        let goal = &problem.goal.clone();
-        let mut next = Some(goal);
-
-        if let Some(node) = nodes.get(goal) {
-            BreathFirstSearch::update_tile(graph, &mut tiles, node);
-        }
-
-        while let Some(tile) = next {
-            match result.get(tile) {
-                Some(parent) => {
-                    if let Some(node) = nodes.get(parent) {
-                        BreathFirstSearch::update_tile(graph, &mut tiles, node);
-                    }
-                    next = Some(parent);
-                }
-                _ => {
-                    next = None;
-                }
-            }
+        for position in result.path.iter() {
+            tiles[graph.get_index_at_position(*position)] = 1;
         }
         tiles
     }
-
-    fn update_tile(graph: &Graph, tiles: &mut Vec<u8>, node: &Node) {
-        let position: Position = node.position;
-        let index = graph.get_index_at_position(position);
-        tiles[index] = 1;
-    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::graph::Graph;
+    use crate::node::Node;
+    use crate::position::Position;
+    use crate::tile::Tile;
 
     use super::*;
 
     #[test]
     fn search_with_valid_path_returns_result() {
         let tiles: Vec<u8> = vec![2, 1, 2, 2, 1, 2, 2, 1, 2];
-        let graph = Graph::new(tiles, 3, 3);
+        let graph = Graph::new(tiles, 3, 3).unwrap();
         let problem = Problem::new(
             graph.get_path_nodes(),
-            "0-1".to_string(),
-            "2-1".to_string(),
+            graph.get_node_id_at_position(Position::new(0, 1)),
+            graph.get_node_id_at_position(Position::new(2, 1)),
             graph.size,
-        );
-        let result = BreathFirstSearch::search(&problem);
+        )
+        .unwrap();
+        let result = BreathFirstSearch::search(&problem).unwrap();
         let unwrap_result = result.unwrap();
 
-        assert_eq!("1-1".to_string(), unwrap_result["2-1"]);
-        assert_eq!("0-1".to_string(), unwrap_result["1-1"]);
-        assert_eq!("".to_string(), unwrap_result["0-1"]);
+        assert_eq!(
+            vec![
+                Position::new(0, 1),
+                Position::new(1, 1),
+                Position::new(2, 1),
+            ],
+            unwrap_result.path
+        );
+        assert_eq!(2, unwrap_result.length);
+        assert_eq!(2.0, unwrap_result.cost);
+        assert_eq!(2, unwrap_result.expanded);
     }
 
     #[test]
@@ -184,17 +335,20 @@ mod tests {
             2, 1, 2,
             2, 1, 2,
             2, 1, 2];
-        let graph = Graph::new(tiles, 3, 3);
+        let graph = Graph::new(tiles, 3, 3).unwrap();
         let problem = Problem::new(
             graph.get_path_nodes(),
-            "0-1".to_string(),
-            "0-1".to_string(),
+            graph.get_node_id_at_position(Position::new(0, 1)),
+            graph.get_node_id_at_position(Position::new(0, 1)),
             graph.size,
-        );
-        let result = BreathFirstSearch::search(&problem);
+        )
+        .unwrap();
+        let result = BreathFirstSearch::search(&problem).unwrap();
         let unwrap_result = result.unwrap();
 
-        assert_eq!("".to_string(), unwrap_result["0-1"]);
+        assert_eq!(vec![Position::new(0, 1)], unwrap_result.path);
+        assert_eq!(0, unwrap_result.length);
+        assert_eq!(0, unwrap_result.expanded);
     }
 
     #[test]
@@ -203,14 +357,15 @@ mod tests {
             2, 2, 2, 2, 2, 2, 2, 1, 2, 2, 1, 2, 2, 1, 2, 2, 1, 2, 2, 1, 1, 1, 1, 2, 2, 1, 2, 2, 1,
             2, 2, 1, 2, 2, 2, 2,
         ];
-        let graph = Graph::new(tiles, 6, 6);
+        let graph = Graph::new(tiles, 6, 6).unwrap();
         let problem = Problem::new(
             graph.get_path_nodes(),
-            "1-1".to_string(),
-            "1-4".to_string(),
+            graph.get_node_id_at_position(Position::new(1, 1)),
+            graph.get_node_id_at_position(Position::new(1, 4)),
             graph.size,
-        );
-        let result = BreathFirstSearch::search(&problem);
+        )
+        .unwrap();
+        let result = BreathFirstSearch::search(&problem).unwrap();
         let unwrap_result = result.unwrap();
 
         assert_eq!(
@@ -218,7 +373,47 @@ mod tests {
                 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 1, 0, 0, 1, 0, 0, 1, 0, 0, 1, 1, 1, 1, 0, 0, 0, 0, 0,
                 0, 0, 0, 0, 0, 0, 0, 0
             ],
-            BreathFirstSearch::get_path(&unwrap_result, &graph, &problem)
+            BreathFirstSearch::get_path(&unwrap_result, &graph)
+        );
+    }
+
+    #[test]
+    fn search_without_path_returns_none() {
+        let tiles: Vec<u8> = vec![
+            2, 1, 2,
+            2, 2, 2,
+            2, 1, 2];
+        let graph = Graph::new(tiles, 3, 3).unwrap();
+        let problem = Problem::new(
+            graph.get_path_nodes(),
+            graph.get_node_id_at_position(Position::new(0, 1)),
+            graph.get_node_id_at_position(Position::new(2, 1)),
+            graph.size,
+        )
+        .unwrap();
+
+        assert_eq!(None, BreathFirstSearch::search(&problem).unwrap());
+    }
+
+    #[test]
+    fn search_with_unknown_start_returns_error() {
+        let tiles: Vec<u8> = vec![
+            2, 1, 2,
+            2, 1, 2,
+            2, 1, 2];
+        let graph = Graph::new(tiles, 3, 3).unwrap();
+        let mut problem = Problem::new(
+            graph.get_path_nodes(),
+            graph.get_node_id_at_position(Position::new(0, 1)),
+            graph.get_node_id_at_position(Position::new(2, 1)),
+            graph.size,
+        )
+        .unwrap();
+        problem.start = NodeId(42);
+
+        assert_eq!(
+            Err(SearchError::UnknownStart(NodeId(42))),
+            BreathFirstSearch::search(&problem)
         );
     }
 
@@ -232,14 +427,15 @@ mod tests {
             0, 0, 0, 1, 1, 2,
             0, 0, 0, 2, 2, 2,
         ];
-        let graph = Graph::new(tiles, 6, 6);
+        let graph = Graph::new(tiles, 6, 6).unwrap();
         let problem = Problem::new(
             graph.get_possible_nodes(),
-            "1-1".to_string(),
-            "4-4".to_string(),
+            graph.get_node_id_at_position(Position::new(1, 1)),
+            graph.get_node_id_at_position(Position::new(4, 4)),
             graph.size,
-        );
-        let result = BreathFirstSearch::search(&problem);
+        )
+        .unwrap();
+        let result = BreathFirstSearch::search(&problem).unwrap();
         let unwrap_result = result.unwrap();
 
         assert_eq!(
@@ -251,7 +447,134 @@ mod tests {
                 0, 0, 1, 1, 1, 0,
                 0, 0, 0, 0, 0, 0,
             ],
-            BreathFirstSearch::get_path(&unwrap_result, &graph, &problem)
+            BreathFirstSearch::get_path(&unwrap_result, &graph)
+        );
+    }
+
+    #[test]
+    fn search_bidirectional_returns_same_length_as_search() {
+        let tiles: Vec<u8> = vec![
+            1, 1, 1, 1, 1, 1,
+            1, 2, 2, 2, 2, 1,
+            1, 1, 1, 2, 1, 1,
+            2, 2, 1, 2, 1, 2,
+            1, 1, 1, 1, 1, 1,
+            1, 2, 2, 2, 2, 1,
+        ];
+        let graph = Graph::new(tiles, 6, 6).unwrap();
+        for goal in [
+            Position::new(5, 0),
+            Position::new(2, 4),
+            Position::new(5, 5),
+        ]
+        .iter()
+        {
+            let problem = Problem::new(
+                graph.get_path_nodes(),
+                graph.get_node_id_at_position(Position::new(0, 0)),
+                graph.get_node_id_at_position(*goal),
+                graph.size,
+            )
+            .unwrap();
+            let result = BreathFirstSearch::search(&problem).unwrap().unwrap();
+            let bidirectional_result = BreathFirstSearch::search_bidirectional(&problem)
+                .unwrap()
+                .unwrap();
+
+            assert_eq!(result.length, bidirectional_result.length);
+            assert_eq!(Position::new(0, 0), bidirectional_result.path[0]);
+            assert_eq!(*goal, bidirectional_result.path[result.length]);
+            assert!(bidirectional_result
+                .path
+                .windows(2)
+                .all(|step| step[0].distance(&step[1]) == 1));
+        }
+    }
+
+    #[test]
+    fn search_bidirectional_expands_fewer_nodes() {
+        let graph = Graph::new(vec![1; 41 * 41], 41, 41).unwrap();
+        let problem = Problem::new(
+            graph.get_path_nodes(),
+            graph.get_node_id_at_position(Position::new(20, 4)),
+            graph.get_node_id_at_position(Position::new(20, 36)),
+            graph.size,
+        )
+        .unwrap();
+        let result = BreathFirstSearch::search(&problem).unwrap().unwrap();
+        let bidirectional_result = BreathFirstSearch::search_bidirectional(&problem)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(32, bidirectional_result.length);
+        assert!(bidirectional_result.expanded * 4 < result.expanded * 3);
+        assert_eq!(
+            BreathFirstSearch::get_path(&result, &graph)
+                .iter()
+                .sum::<u8>(),
+            BreathFirstSearch::get_path(&bidirectional_result, &graph)
+                .iter()
+                .sum::<u8>()
+        );
+    }
+
+    #[test]
+    fn search_bidirectional_follows_one_way_edges() {
+        let node =
+            |column, neighbours| Node::new(Position::new(0, column), Tile::Path, true, neighbours);
+        // 0 -> 1 -> 2 -> 3 and a one-way shortcut 3 -> 0
+        let nodes = vec![
+            node(0, vec![NodeId(1)]),
+            node(1, vec![NodeId(2)]),
+            node(2, vec![NodeId(3)]),
+            node(3, vec![NodeId(0)]),
+        ];
+        let forward = Problem::new(nodes.clone(), NodeId(0), NodeId(3), 4).unwrap();
+        let backward = Problem::new(nodes, NodeId(3), NodeId(1), 4).unwrap();
+
+        assert_eq!(
+            3,
+            BreathFirstSearch::search_bidirectional(&forward)
+                .unwrap()
+                .unwrap()
+                .length
+        );
+        assert_eq!(
+            vec![
+                Position::new(0, 3),
+                Position::new(0, 0),
+                Position::new(0, 1)
+            ],
+            BreathFirstSearch::search_bidirectional(&backward)
+                .unwrap()
+                .unwrap()
+                .path
         );
     }
+
+    #[test]
+    fn search_bidirectional_against_one_way_edge_returns_none() {
+        let nodes = vec![
+            Node::new(Position::new(0, 0), Tile::Path, true, vec![NodeId(1)]),
+            Node::new(Position::new(0, 1), Tile::Path, true, vec![]),
+        ];
+        let problem = Problem::new(nodes, NodeId(1), NodeId(0), 2).unwrap();
+
+        assert_eq!(
+            None,
+            BreathFirstSearch::search_bidirectional(&problem).unwrap()
+        );
+    }
+
+    #[test]
+    fn search_bidirectional_with_start_equals_goal_returns_result() {
+        let graph = Graph::new(vec![1, 1], 2, 1).unwrap();
+        let problem = Problem::new(graph.get_path_nodes(), NodeId(1), NodeId(1), 2).unwrap();
+        let result = BreathFirstSearch::search_bidirectional(&problem)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(vec![Position::new(0, 1)], result.path);
+        assert_eq!(0, result.length);
+    }
 }
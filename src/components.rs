@@ -0,0 +1,174 @@
+use crate::graph::Graph;
+use crate::node::Node;
+use crate::position::Position;
+
+#[derive(Debug, Clone, PartialEq)]
+/// The connected regions of walkable nodes, each one labeled with a number
+///
+/// A path exists between two positions if and only if both are in the same region,
+/// so impossible problems are recognized without a search.
+///
+/// # Example
+///
+/// ```
+/// use rust_problem_search::graph::Graph;
+/// use rust_problem_search::position::Position;
+///
+/// let graph = Graph::new(vec![1, 2, 1, 1, 2, 1], 3, 2).unwrap();
+/// let components = graph.get_path_components();
+///
+/// assert_eq!(2, components.count());
+/// assert!(components.is_connected(Position::new(0, 0), Position::new(1, 0)));
+/// assert!(!components.is_connected(Position::new(0, 0), Position::new(0, 2)));
+/// ```
+pub struct Components {
+    labels: Vec<Option<usize>>,
+    count: usize,
+    width: u32,
+    height: u32,
+}
+
+impl Components {
+    /// Returns the regions of the nodes, two walkable nodes are in the same region
+    /// if one is reached from the other by stepping from neighbour to neighbour
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - The graph the nodes are build from
+    /// * `nodes` - The nodes indexed by NodeId, e.g. the nodes of a `MutableGraph`
+    pub fn new(graph: &Graph, nodes: &[Node]) -> Self {
+        let mut labels = vec![None; nodes.len()];
+        let mut count = 0;
+        let mut stack = vec![];
+        for index in 0..nodes.len() {
+            if labels[index].is_some() || !nodes[index].walkable {
+                continue;
+            }
+            labels[index] = Some(count);
+            stack.push(index);
+            while let Some(current) = stack.pop() {
+                for next in &nodes[current].neighbours {
+                    if labels[next.index()].is_none() && nodes[next.index()].walkable {
+                        labels[next.index()] = Some(count);
+                        stack.push(next.index());
+                    }
+                }
+            }
+            count += 1;
+        }
+        Components {
+            labels,
+            count,
+            width: graph.width,
+            height: graph.height,
+        }
+    }
+
+    /// Returns the number of regions
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the region of each tile, None for tiles which are not walkable
+    pub fn get_labels(&self) -> &[Option<usize>] {
+        &self.labels
+    }
+
+    /// Returns the region of the tile at a position,
+    /// None if the tile is not walkable or outside of the graph
+    pub fn get_component(&self, position: Position) -> Option<usize> {
+        if position.row >= self.height || position.column >= self.width {
+            return None;
+        }
+        self.labels[(position.row * self.width + position.column) as usize]
+    }
+
+    /// Returns true if both positions are walkable and in the same region
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The first position
+    /// * `to` - The second position
+    pub fn is_connected(&self, from: Position, to: Position) -> bool {
+        match (self.get_component(from), self.get_component(to)) {
+            (Some(from), Some(to)) => from == to,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::breath_first_search::BreathFirstSearch;
+    use crate::connectivity::Connectivity;
+    use crate::problem::Problem;
+
+    fn map() -> &'static str {
+        "
+..#.#
+..# #
+###.#
+  #..
+"
+    }
+
+    #[test]
+    fn new_with_path_nodes_labels_path_regions() {
+        let graph = Graph::from_ascii(map()).unwrap().graph;
+        let components = graph.get_path_components();
+
+        assert_eq!(3, components.count());
+        assert_eq!(Some(0), components.get_component(Position::new(1, 1)));
+        assert_eq!(Some(1), components.get_component(Position::new(0, 3)));
+        assert_eq!(Some(2), components.get_component(Position::new(3, 4)));
+        assert_eq!(None, components.get_component(Position::new(1, 3)));
+        assert_eq!(None, components.get_component(Position::new(0, 2)));
+        assert_eq!(None, components.get_component(Position::new(4, 0)));
+    }
+
+    #[test]
+    fn new_with_possible_nodes_labels_path_and_empty_regions() {
+        let graph = Graph::from_ascii(map()).unwrap().graph;
+        let components = graph.get_possible_components();
+
+        assert_eq!(3, components.count());
+        assert!(components.is_connected(Position::new(0, 3), Position::new(3, 4)));
+        assert!(components.is_connected(Position::new(3, 0), Position::new(3, 1)));
+        assert!(!components.is_connected(Position::new(0, 0), Position::new(3, 0)));
+        assert!(!components.is_connected(Position::new(0, 0), Position::new(0, 2)));
+    }
+
+    #[test]
+    fn is_connected_matches_breath_first_search() {
+        for connectivity in [
+            Connectivity::Four,
+            Connectivity::Eight,
+            Connectivity::EightWithoutCornerCutting,
+        ] {
+            let graph = Graph::from_ascii(map())
+                .unwrap()
+                .graph
+                .with_connectivity(connectivity);
+            let nodes = graph.get_possible_nodes();
+            let components = Components::new(&graph, &nodes);
+            let walkable: Vec<&Node> = nodes.iter().filter(|node| node.walkable).collect();
+
+            for from in &walkable {
+                for to in &walkable {
+                    let problem = Problem::new(
+                        nodes.clone(),
+                        graph.get_node_id_at_position(from.position),
+                        graph.get_node_id_at_position(to.position),
+                        graph.size,
+                    )
+                    .unwrap();
+                    assert_eq!(
+                        BreathFirstSearch::search(&problem).unwrap().is_some(),
+                        components.is_connected(from.position, to.position)
+                    );
+                }
+            }
+        }
+    }
+}
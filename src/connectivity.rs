@@ -0,0 +1,68 @@
+#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone, Default)]
+/// Defines which tiles around a tile are its neighbours
+pub enum Connectivity {
+    /// The tiles above, right, below and left
+    #[default]
+    Four,
+    /// The four tiles of `Four` and the four diagonal tiles
+    Eight,
+    /// Like `Eight`, but a diagonal tile is only a neighbour
+    /// if both tiles next to the diagonal move are walkable
+    EightWithoutCornerCutting,
+}
+
+impl Connectivity {
+    /// Returns the row and column offsets of the neighbours,
+    /// the straight neighbours first, the diagonal ones after
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::rust_problem_search::connectivity::Connectivity;
+    ///
+    /// assert_eq!(4, Connectivity::Four.offsets().len());
+    /// assert_eq!(8, Connectivity::Eight.offsets().len());
+    /// ```
+    pub fn offsets(&self) -> &'static [(i32, i32)] {
+        const OFFSETS: [(i32, i32); 8] = [
+            (-1, 0),
+            (0, 1),
+            (1, 0),
+            (0, -1),
+            (-1, 1),
+            (1, 1),
+            (1, -1),
+            (-1, -1),
+        ];
+        match self {
+            Connectivity::Four => &OFFSETS[..4],
+            Connectivity::Eight | Connectivity::EightWithoutCornerCutting => &OFFSETS,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offsets_with_four_returns_straight_offsets() {
+        assert_eq!(
+            &[(-1, 0), (0, 1), (1, 0), (0, -1)],
+            Connectivity::Four.offsets()
+        );
+    }
+
+    #[test]
+    fn offsets_with_eight_returns_diagonal_offsets_last() {
+        let offsets = Connectivity::EightWithoutCornerCutting.offsets();
+
+        assert_eq!(8, offsets.len());
+        assert_eq!(&[(-1, 1), (1, 1), (1, -1), (-1, -1)], &offsets[4..]);
+    }
+
+    #[test]
+    fn default_returns_four() {
+        assert_eq!(Connectivity::Four, Connectivity::default());
+    }
+}
@@ -0,0 +1,465 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+
+use crate::connectivity::Connectivity;
+use crate::graph::Graph;
+use crate::node::{Node, NodeId};
+use crate::position::Position;
+use crate::search_error::SearchError;
+use crate::search_result::SearchResult;
+use crate::tile::Tile;
+
+/// The priority of a node in the queue, compared by the first value and then the second one
+type Key = (f64, f64);
+
+#[derive(Debug, Clone)]
+/// An entry in the queue, the node with the lowest key comes first
+struct Entry {
+    key: Key,
+    index: usize,
+}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_keys(&other.key, &self.key).then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn compare_keys(key: &Key, other: &Key) -> Ordering {
+    key.0
+        .total_cmp(&other.0)
+        .then_with(|| key.1.total_cmp(&other.1))
+}
+
+#[derive(Debug, Clone)]
+/// An incremental planner which keeps its search state between calls
+///
+/// The planner searches backwards from the goal, so after tiles change or the start moves
+/// only the nodes whose cost to the goal changed are expanded again.
+///
+/// # Example
+/// ```
+/// use rust_problem_search::d_star_lite::DStarLite;
+/// use rust_problem_search::graph::Graph;
+/// use rust_problem_search::position::Position;
+/// use rust_problem_search::tile::Tile;
+///
+/// let graph = Graph::new(vec![1; 12], 4, 3).unwrap();
+/// let mut planner =
+///     DStarLite::new(graph, &[Tile::Path], Position::new(1, 0), Position::new(1, 3)).unwrap();
+///
+/// assert_eq!(3, planner.get_path().unwrap().length);
+///
+/// planner.move_to(Position::new(1, 1)).unwrap();
+/// planner.set_tile(Position::new(1, 2), Tile::Ground).unwrap();
+///
+/// assert_eq!(4, planner.get_path().unwrap().length);
+/// ```
+pub struct DStarLite {
+    graph: Graph,
+    tiles: HashSet<Tile>,
+    nodes: Vec<Node>,
+    start: NodeId,
+    goal: NodeId,
+    /// The start when the key modifier was last raised
+    last: NodeId,
+    /// Added to the keys, so keys computed before the start moved stay lower bounds
+    key_modifier: f64,
+    costs: Vec<f64>,
+    lookaheads: Vec<f64>,
+    queue: BinaryHeap<Entry>,
+    /// The current key of each node in the queue, entries with another key are outdated
+    keys: Vec<Option<Key>>,
+}
+
+impl DStarLite {
+    /// Returns a new planner
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - The graph to plan on, the planner owns it to apply the tile changes
+    /// * `tiles` - The walkable tile kinds, e.g. path tiles only or path and empty tiles
+    /// * `start` - The position where the path starts
+    /// * `goal` - The position where the path ends
+    ///
+    /// Returns an error if the start or the goal is outside of the graph or not walkable
+    pub fn new(
+        graph: Graph,
+        tiles: &[Tile],
+        start: Position,
+        goal: Position,
+    ) -> Result<Self, SearchError> {
+        for position in [start, goal] {
+            if !graph.contains(position) {
+                return Err(SearchError::PositionOutOfBounds(position));
+            }
+        }
+        let tiles: HashSet<Tile> = tiles.iter().copied().collect();
+        let nodes = graph.get_neighbours_with_tile(&tiles);
+        let (start, goal) = (
+            graph.get_node_id_at_position(start),
+            graph.get_node_id_at_position(goal),
+        );
+        if !nodes[start.index()].walkable {
+            return Err(SearchError::StartNotWalkable(nodes[start.index()].position));
+        }
+        if !nodes[goal.index()].walkable {
+            return Err(SearchError::GoalNotWalkable(nodes[goal.index()].position));
+        }
+
+        let mut planner = DStarLite {
+            costs: vec![f64::INFINITY; graph.size],
+            lookaheads: vec![f64::INFINITY; graph.size],
+            keys: vec![None; graph.size],
+            queue: BinaryHeap::new(),
+            graph,
+            tiles,
+            nodes,
+            start,
+            goal,
+            last: start,
+            key_modifier: 0.0,
+        };
+        planner.lookaheads[goal.index()] = 0.0;
+        planner.push(goal.index());
+        Ok(planner)
+    }
+
+    /// Returns the graph with all tile changes applied
+    pub fn get_graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    /// Returns an Option with the cheapest path from the current start to the goal
+    /// and the statistics of this call
+    ///
+    /// Only the nodes affected by the changes since the last call are expanded,
+    /// the first call expands the nodes a backward A* search would expand.
+    pub fn get_path(&mut self) -> Option<SearchResult> {
+        let (expanded, frontier_peak) = self.compute_shortest_path();
+        if self.lookaheads[self.start.index()].is_infinite() {
+            return None;
+        }
+
+        let mut path = vec![self.start];
+        let mut cost = 0.0;
+        while path[path.len() - 1] != self.goal && path.len() <= self.nodes.len() {
+            let (next, step_cost) = self.successors(path[path.len() - 1].index()).min_by(
+                |(next, step_cost), (other, other_step_cost)| {
+                    (step_cost + self.costs[*next])
+                        .total_cmp(&(other_step_cost + self.costs[*other]))
+                },
+            )?;
+            if self.costs[next].is_infinite() {
+                return None;
+            }
+            cost += step_cost;
+            path.push(NodeId::new(next));
+        }
+        Some(SearchResult {
+            length: path.len() - 1,
+            path: path
+                .into_iter()
+                .map(|id| self.nodes[id.index()].position)
+                .collect(),
+            cost,
+            expanded,
+            frontier_peak,
+        })
+    }
+
+    /// Moves the start of the path, e.g. to the next position of a driving robot
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The new start
+    ///
+    /// Returns an error if the position is outside of the graph
+    pub fn move_to(&mut self, position: Position) -> Result<(), SearchError> {
+        if !self.graph.contains(position) {
+            return Err(SearchError::PositionOutOfBounds(position));
+        }
+        self.start = self.graph.get_node_id_at_position(position);
+        self.key_modifier += self.heuristic(self.last, self.start);
+        self.last = self.start;
+        Ok(())
+    }
+
+    /// Changes a tile of the graph and updates the nodes next to it,
+    /// the path is repaired by the next call of `get_path`
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The position of the tile
+    /// * `tile` - The new tile
+    ///
+    /// Returns an error if the position is outside of the graph
+    pub fn set_tile(&mut self, position: Position, tile: Tile) -> Result<(), SearchError> {
+        self.graph.set_tile(position, tile)?;
+
+        // the tile changes the cost to enter it and diagonal moves past it,
+        // so only the edges of the nodes around it change
+        let indices = self
+            .graph
+            .update_nodes_with_tile(&mut self.nodes, &[position], &self.tiles);
+        for index in indices {
+            self.update(index);
+        }
+        Ok(())
+    }
+
+    /// Returns the neighbours of a node with the cost to enter them
+    fn successors(&self, index: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        let node = &self.nodes[index];
+        node.neighbours
+            .iter()
+            .filter_map(move |id| Some((id.index(), self.nodes[id.index()].cost_from(node)?)))
+    }
+
+    /// Returns the manhattan distance, or the octile distance on eight-connected graphs
+    fn heuristic(&self, from: NodeId, to: NodeId) -> f64 {
+        let (from, to) = (
+            self.nodes[from.index()].position,
+            self.nodes[to.index()].position,
+        );
+        if self.graph.connectivity == Connectivity::Four {
+            f64::from(from.distance(&to))
+        } else {
+            from.octile_distance(&to)
+        }
+    }
+
+    fn calculate_key(&self, index: usize) -> Key {
+        let cost = self.costs[index].min(self.lookaheads[index]);
+        (
+            cost + self.heuristic(self.start, NodeId::new(index)) + self.key_modifier,
+            cost,
+        )
+    }
+
+    fn push(&mut self, index: usize) {
+        let key = self.calculate_key(index);
+        self.keys[index] = Some(key);
+        self.queue.push(Entry { key, index });
+    }
+
+    /// Returns the first entry of the queue which is not outdated
+    fn top(&mut self) -> Option<&Entry> {
+        while let Some(entry) = self.queue.peek() {
+            if self.keys[entry.index] == Some(entry.key) {
+                break;
+            }
+            self.queue.pop();
+        }
+        self.queue.peek()
+    }
+
+    /// Recomputes the lookahead of a node from its neighbours
+    /// and queues the node if it differs from the cost
+    fn update(&mut self, index: usize) {
+        if index != self.goal.index() {
+            self.lookaheads[index] = self
+                .successors(index)
+                .map(|(next, step_cost)| step_cost + self.costs[next])
+                .fold(f64::INFINITY, f64::min);
+        }
+        self.keys[index] = None;
+        if self.costs[index] != self.lookaheads[index] {
+            self.push(index);
+        }
+    }
+
+    /// Expands nodes until the cost of the start is final,
+    /// returns the number of expanded nodes and the largest queue size
+    fn compute_shortest_path(&mut self) -> (usize, usize) {
+        let start = self.start.index();
+        let mut expanded = 0;
+        let mut frontier_peak = self.queue.len();
+
+        while let Some(entry) = self.top() {
+            let (key, index) = (entry.key, entry.index);
+            if compare_keys(&key, &self.calculate_key(start)) != Ordering::Less
+                && self.lookaheads[start] == self.costs[start]
+            {
+                break;
+            }
+            self.queue.pop();
+            self.keys[index] = None;
+
+            let new_key = self.calculate_key(index);
+            if compare_keys(&key, &new_key) == Ordering::Less {
+                self.push(index);
+            } else {
+                expanded += 1;
+                if self.costs[index] > self.lookaheads[index] {
+                    self.costs[index] = self.lookaheads[index];
+                } else {
+                    self.costs[index] = f64::INFINITY;
+                    self.update(index);
+                }
+                // the neighbours are the predecessors as well, since all moves go both ways
+                let predecessors = self.nodes[index].neighbours.clone();
+                for predecessor in predecessors {
+                    self.update(predecessor.index());
+                }
+            }
+            frontier_peak = frontier_peak.max(self.queue.len());
+        }
+        (expanded, frontier_peak)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::problem::Problem;
+    use crate::uniform_cost_search::UniformCostSearch;
+
+    fn map() -> Graph {
+        Graph::from_ascii(
+            "
+..........
+..........
+..........
+..........
+..........
+..........
+",
+        )
+        .unwrap()
+        .graph
+    }
+
+    fn uniform_cost(graph: &Graph, tiles: &[Tile], start: Position, goal: Position) -> Option<f64> {
+        let nodes = if tiles.contains(&Tile::None) {
+            graph.get_possible_nodes()
+        } else {
+            graph.get_path_nodes()
+        };
+        let problem = Problem::new(
+            nodes,
+            graph.get_node_id_at_position(start),
+            graph.get_node_id_at_position(goal),
+            graph.size,
+        )
+        .ok()?;
+        UniformCostSearch::search(&problem)
+            .unwrap()
+            .map(|result| result.cost)
+    }
+
+    #[test]
+    fn get_path_returns_cheapest_path() {
+        let (start, goal) = (Position::new(0, 0), Position::new(5, 9));
+        let mut planner = DStarLite::new(map(), &[Tile::Path], start, goal).unwrap();
+        let result = planner.get_path().unwrap();
+
+        assert_eq!(start, result.path[0]);
+        assert_eq!(goal, result.path[result.length]);
+        assert_eq!(14, result.length);
+        assert_eq!(
+            Some(result.cost),
+            uniform_cost(&map(), &[Tile::Path], start, goal)
+        );
+    }
+
+    #[test]
+    fn get_path_after_changes_repairs_path_with_fewer_expansions() {
+        let goal = Position::new(5, 9);
+        let tiles = [Tile::Path, Tile::None];
+        let mut planner = DStarLite::new(
+            map().with_connectivity(Connectivity::EightWithoutCornerCutting),
+            &tiles,
+            Position::new(0, 0),
+            goal,
+        )
+        .unwrap();
+        let mut result = planner.get_path().unwrap();
+
+        let changes = [
+            (Position::new(3, 3), Tile::Ground),
+            (Position::new(4, 4), Tile::Ground),
+            (Position::new(2, 5), Tile::None),
+            (Position::new(4, 6), Tile::Ground),
+        ];
+        for (position, tile) in changes {
+            planner.move_to(result.path[1]).unwrap();
+            planner.set_tile(position, tile).unwrap();
+            result = planner.get_path().unwrap();
+
+            let mut fresh_planner =
+                DStarLite::new(planner.get_graph().clone(), &tiles, result.path[0], goal).unwrap();
+            let fresh_result = fresh_planner.get_path().unwrap();
+
+            assert!((fresh_result.cost - result.cost).abs() < 1e-9);
+            let expected = uniform_cost(planner.get_graph(), &tiles, result.path[0], goal).unwrap();
+            assert!((expected - result.cost).abs() < 1e-9);
+            assert!(result.expanded < fresh_result.expanded);
+        }
+    }
+
+    #[test]
+    fn get_path_after_opening_a_tile_returns_shorter_path() {
+        let map = Graph::from_ascii("...\n.#.\n.#.\n...\n").unwrap().graph;
+        let mut planner =
+            DStarLite::new(map, &[Tile::Path], Position::new(1, 0), Position::new(1, 2)).unwrap();
+
+        assert_eq!(4, planner.get_path().unwrap().length);
+
+        planner.set_tile(Position::new(1, 1), Tile::Path).unwrap();
+
+        assert_eq!(2, planner.get_path().unwrap().length);
+    }
+
+    #[test]
+    fn get_path_after_blocking_the_way_returns_none() {
+        let map = Graph::from_ascii("...\n").unwrap().graph;
+        let mut planner =
+            DStarLite::new(map, &[Tile::Path], Position::new(0, 0), Position::new(0, 2)).unwrap();
+
+        assert!(planner.get_path().is_some());
+
+        planner.set_tile(Position::new(0, 1), Tile::Ground).unwrap();
+
+        assert_eq!(None, planner.get_path());
+
+        planner.set_tile(Position::new(0, 1), Tile::None).unwrap();
+
+        assert_eq!(None, planner.get_path());
+    }
+
+    #[test]
+    fn new_with_invalid_positions_returns_error() {
+        let map = Graph::from_ascii("#..\n").unwrap().graph;
+
+        assert_eq!(
+            SearchError::StartNotWalkable(Position::new(0, 0)),
+            DStarLite::new(
+                map.clone(),
+                &[Tile::Path],
+                Position::new(0, 0),
+                Position::new(0, 2)
+            )
+            .unwrap_err()
+        );
+        assert_eq!(
+            SearchError::PositionOutOfBounds(Position::new(1, 2)),
+            DStarLite::new(map, &[Tile::Path], Position::new(0, 1), Position::new(1, 2))
+                .unwrap_err()
+        );
+    }
+}
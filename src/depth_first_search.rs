@@ -0,0 +1,184 @@
+use crate::problem::Problem;
+use crate::search_error::SearchError;
+use crate::search_problem::{SearchProblem, SearchTree};
+use crate::search_result::SearchResult;
+
+#[derive(Debug, Clone)]
+pub struct DepthFirstSearch {}
+
+impl DepthFirstSearch {
+    /// Returns an Option with the first path found and the search statistics,
+    /// or an error if the problem is invalid
+    ///
+    /// The path is not the shortest one, the search is meant for quick checks
+    /// if the goal is reachable at all.
+    ///
+    /// # Arguments
+    ///
+    /// * `problem` The problem contains the start, the end and the nodes where to find the path
+    ///
+    /// # Example
+    /// ```
+    /// use rust_problem_search::depth_first_search::DepthFirstSearch;
+    /// use rust_problem_search::graph::Graph;
+    ///
+    /// let ascii_map = Graph::from_ascii("S.#\n..#\n#.G\n").unwrap();
+    /// let problem = ascii_map.get_path_problem().unwrap();
+    ///
+    /// assert!(DepthFirstSearch::search(&problem).unwrap().is_some());
+    /// ```
+    pub fn search(problem: &Problem) -> Result<Option<SearchResult>, SearchError> {
+        problem.validate()?;
+        Ok(DepthFirstSearch::search_problem(problem, None)
+            .map(|result| result.map(|id| problem.get_position(id))))
+    }
+
+    /// Returns an Option with the first path of at most `depth_limit` steps
+    /// and the search statistics, or an error if the problem is invalid
+    ///
+    /// # Arguments
+    ///
+    /// * `problem` The problem contains the start, the end and the nodes where to find the path
+    /// * `depth_limit` The largest number of steps from the start
+    pub fn search_with_limit(
+        problem: &Problem,
+        depth_limit: usize,
+    ) -> Result<Option<SearchResult>, SearchError> {
+        problem.validate()?;
+        Ok(DepthFirstSearch::search_problem(problem, Some(depth_limit))
+            .map(|result| result.map(|id| problem.get_position(id))))
+    }
+
+    /// Returns an Option with the first path of states found to a goal and the search statistics
+    ///
+    /// Without a limit each state is expanded at most once. With a limit a state reached again
+    /// on a shorter branch is expanded again, so the limit never hides a goal within the limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `problem` Any problem with an initial state, a goal test and successors
+    /// * `depth_limit` The largest number of steps from the initial state, None for no limit
+    pub fn search_problem<P: SearchProblem>(
+        problem: &P,
+        depth_limit: Option<usize>,
+    ) -> Option<SearchResult<P::State>> {
+        let mut tree = SearchTree::new(problem);
+        let mut depths = vec![0];
+        let mut frontier = vec![0];
+        let mut frontier_peak = frontier.len();
+        let mut expanded = 0;
+
+        while let Some(index) = frontier.pop() {
+            if problem.is_goal(tree.state(index)) {
+                return Some(SearchResult::from_tree(
+                    &tree,
+                    index,
+                    expanded,
+                    frontier_peak,
+                ));
+            }
+            let depth = depths[index];
+            if depth_limit.is_some_and(|limit| depth >= limit) {
+                continue;
+            }
+            expanded += 1;
+
+            let cost = tree.cost(index);
+            // pushed in reverse, so the first successor is taken first
+            for (state, step_cost) in problem.successors(tree.state(index)).into_iter().rev() {
+                let next = match tree.get(&state) {
+                    Some(_) if depth_limit.is_none() => continue,
+                    Some(next) if depths[next] <= depth + 1 => continue,
+                    Some(next) => {
+                        tree.update(next, index, cost + step_cost);
+                        depths[next] = depth + 1;
+                        next
+                    }
+                    None => {
+                        depths.push(depth + 1);
+                        tree.insert(state, Some(index), cost + step_cost)
+                    }
+                };
+                frontier.push(next);
+                frontier_peak = frontier_peak.max(frontier.len());
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Graph;
+    use crate::position::Position;
+
+    fn map() -> Problem {
+        let map = "
+.....
+.###.
+S....
+####.
+G....
+";
+        Graph::from_ascii(map).unwrap().get_path_problem().unwrap()
+    }
+
+    #[test]
+    fn search_returns_connected_path() {
+        let result = DepthFirstSearch::search(&map()).unwrap().unwrap();
+
+        assert_eq!(14, result.length);
+        assert_eq!(Position::new(2, 0), result.path[0]);
+        assert_eq!(Position::new(4, 0), result.path[result.length]);
+        assert!(result
+            .path
+            .windows(2)
+            .all(|step| step[0].distance(&step[1]) == 1));
+        assert_eq!(result.length as f64, result.cost);
+    }
+
+    #[test]
+    fn search_with_limit_expands_nodes_again_on_shorter_branch() {
+        let result = DepthFirstSearch::search_with_limit(&map(), 10)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(10, result.length);
+        assert_eq!(10.0, result.cost);
+        assert_eq!(Position::new(2, 1), result.path[1]);
+    }
+
+    #[test]
+    fn search_with_limit_below_shortest_path_returns_none() {
+        assert_eq!(
+            None,
+            DepthFirstSearch::search_with_limit(&map(), 9).unwrap()
+        );
+    }
+
+    #[test]
+    fn search_without_limit_expands_each_node_once() {
+        let graph = Graph::new(vec![1; 400], 20, 20).unwrap();
+        let problem = Problem::new(
+            graph.get_path_nodes(),
+            graph.get_node_id_at_position(Position::new(0, 0)),
+            graph.get_node_id_at_position(Position::new(1, 0)),
+            graph.size,
+        )
+        .unwrap();
+        let result = DepthFirstSearch::search(&problem).unwrap().unwrap();
+
+        assert!(result.expanded < graph.size);
+    }
+
+    #[test]
+    fn search_without_path_returns_none() {
+        let problem = Graph::from_ascii("S#G")
+            .unwrap()
+            .get_path_problem()
+            .unwrap();
+
+        assert_eq!(None, DepthFirstSearch::search(&problem).unwrap());
+    }
+}
@@ -0,0 +1,297 @@
+use std::collections::{BinaryHeap, VecDeque};
+
+use crate::a_star_search::State;
+use crate::graph::Graph;
+use crate::node::{Node, NodeId};
+use crate::position::Position;
+use crate::search_error::SearchError;
+
+#[derive(Debug, Clone)]
+pub struct DistanceField {}
+
+impl DistanceField {
+    /// Returns the number of steps from the start to each tile, aligned with `Graph::tiles`,
+    /// None for tiles without a path from the start, or an error if the start is invalid
+    ///
+    /// The breath first search runs until all reachable nodes are expanded, so the distances
+    /// answer range queries like all tiles within a number of steps.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` The graph the nodes are build from
+    /// * `nodes` The nodes to search, e.g. `graph.get_path_nodes()`
+    /// * `start` The position the distances are measured from
+    ///
+    /// # Example
+    /// ```
+    /// use rust_problem_search::distance_field::DistanceField;
+    /// use rust_problem_search::graph::Graph;
+    /// use rust_problem_search::position::Position;
+    ///
+    /// let graph = Graph::new(vec![1, 1, 2, 1], 4, 1).unwrap();
+    ///
+    /// let steps =
+    ///     DistanceField::get_steps(&graph, &graph.get_path_nodes(), Position::new(0, 1)).unwrap();
+    ///
+    /// assert_eq!(vec![Some(1), Some(0), None, None], steps);
+    /// ```
+    pub fn get_steps(
+        graph: &Graph,
+        nodes: &[Node],
+        start: Position,
+    ) -> Result<Vec<Option<u32>>, SearchError> {
+        let start = DistanceField::get_node_id(graph, nodes, start, SearchError::StartNotWalkable)?;
+        let mut steps = vec![None; nodes.len()];
+        let mut frontier = VecDeque::new();
+        steps[start.index()] = Some(0);
+        frontier.push_back(start);
+
+        while let Some(id) = frontier.pop_front() {
+            let node = &nodes[id.index()];
+            let next_steps = steps[id.index()].map(|steps| steps + 1);
+            for next in &node.neighbours {
+                if steps[next.index()].is_none() && nodes[next.index()].cost_from(node).is_some() {
+                    steps[next.index()] = next_steps;
+                    frontier.push_back(*next);
+                }
+            }
+        }
+        Ok(steps)
+    }
+
+    /// Returns the cost of the cheapest path from the start to each tile,
+    /// aligned with `Graph::tiles`, None for tiles without a path from the start,
+    /// or an error if the start is invalid
+    ///
+    /// The costs are the ones of the uniform cost search, the sum of the costs to enter each
+    /// tile of the path. They are no whole numbers if diagonal steps cost the square root of two.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` The graph the nodes are build from
+    /// * `nodes` The nodes to search, e.g. `graph.get_possible_nodes()`
+    /// * `start` The position the costs are measured from
+    ///
+    /// # Example
+    /// ```
+    /// use rust_problem_search::distance_field::DistanceField;
+    /// use rust_problem_search::graph::Graph;
+    /// use rust_problem_search::position::Position;
+    ///
+    /// let graph = Graph::new(vec![1, 0, 1, 2], 4, 1).unwrap();
+    ///
+    /// let costs =
+    ///     DistanceField::get_costs(&graph, &graph.get_possible_nodes(), Position::new(0, 0))
+    ///         .unwrap();
+    ///
+    /// assert_eq!(vec![Some(0.0), Some(3.0), Some(4.0), None], costs);
+    /// ```
+    pub fn get_costs(
+        graph: &Graph,
+        nodes: &[Node],
+        start: Position,
+    ) -> Result<Vec<Option<f64>>, SearchError> {
+        let start = DistanceField::get_node_id(graph, nodes, start, SearchError::StartNotWalkable)?;
+        Ok(DistanceField::get_costs_with_step(
+            nodes,
+            start,
+            |from, to| to.cost_from(from),
+        ))
+    }
+
+    /// Returns the cost of the cheapest path from each tile to the goal,
+    /// aligned with `Graph::tiles`, None for tiles without a path to the goal,
+    /// or an error if the goal is invalid
+    ///
+    /// The costs to enter the tiles are counted in the direction towards the goal,
+    /// so the cost of a tile is the one of a path which starts there.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` The graph the nodes are build from
+    /// * `nodes` The nodes to search, e.g. `graph.get_possible_nodes()`
+    /// * `goal` The position the costs are measured to
+    pub fn get_costs_to_goal(
+        graph: &Graph,
+        nodes: &[Node],
+        goal: Position,
+    ) -> Result<Vec<Option<f64>>, SearchError> {
+        let goal = DistanceField::get_node_id(graph, nodes, goal, SearchError::GoalNotWalkable)?;
+        Ok(DistanceField::get_costs_with_step(
+            nodes,
+            goal,
+            |from, to| from.cost_from(to),
+        ))
+    }
+
+    /// Expands the nodes from the source in the order of their cost,
+    /// `step_cost` returns the cost between an expanded node and its neighbour
+    fn get_costs_with_step<F>(nodes: &[Node], source: NodeId, step_cost: F) -> Vec<Option<f64>>
+    where
+        F: Fn(&Node, &Node) -> Option<f64>,
+    {
+        let mut costs = vec![f64::INFINITY; nodes.len()];
+        let mut frontier = BinaryHeap::new();
+        costs[source.index()] = 0.0;
+        frontier.push(State {
+            estimate: 0.0,
+            cost: 0.0,
+            index: source.index(),
+        });
+
+        while let Some(State { cost, index, .. }) = frontier.pop() {
+            if cost > costs[index] {
+                continue;
+            }
+            let node = &nodes[index];
+            for next in &node.neighbours {
+                let next_cost = match step_cost(node, &nodes[next.index()]) {
+                    Some(step_cost) => cost + step_cost,
+                    None => continue,
+                };
+                if next_cost < costs[next.index()] {
+                    costs[next.index()] = next_cost;
+                    frontier.push(State {
+                        estimate: next_cost,
+                        cost: next_cost,
+                        index: next.index(),
+                    });
+                }
+            }
+        }
+        costs
+            .into_iter()
+            .map(|cost| Some(cost).filter(|cost| cost.is_finite()))
+            .collect()
+    }
+
+    fn get_node_id(
+        graph: &Graph,
+        nodes: &[Node],
+        position: Position,
+        not_walkable: fn(Position) -> SearchError,
+    ) -> Result<NodeId, SearchError> {
+        if nodes.len() != graph.size {
+            return Err(SearchError::InvalidTileCount {
+                expected: graph.size,
+                actual: nodes.len(),
+            });
+        }
+        if !graph.contains(position) {
+            return Err(SearchError::PositionOutOfBounds(position));
+        }
+        let id = graph.get_node_id_at_position(position);
+        if !nodes[id.index()].walkable {
+            return Err(not_walkable(position));
+        }
+        Ok(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::breath_first_search::BreathFirstSearch;
+    use crate::connectivity::Connectivity;
+    use crate::problem::Problem;
+    use crate::uniform_cost_search::UniformCostSearch;
+
+    fn map() -> &'static str {
+        "
+.... .
+.##. #
+.  . .
+##.#..
+"
+    }
+
+    #[test]
+    fn get_steps_matches_breath_first_search() {
+        let graph = Graph::from_ascii(map()).unwrap().graph;
+        let nodes = graph.get_path_nodes();
+        let start = Position::new(0, 0);
+        let steps = DistanceField::get_steps(&graph, &nodes, start).unwrap();
+
+        for (index, node) in nodes.iter().enumerate() {
+            let expected = if node.walkable {
+                let problem = Problem::new(
+                    nodes.clone(),
+                    graph.get_node_id_at_position(start),
+                    NodeId::new(index),
+                    graph.size,
+                )
+                .unwrap();
+                BreathFirstSearch::search(&problem)
+                    .unwrap()
+                    .map(|result| result.length as u32)
+            } else {
+                None
+            };
+            assert_eq!(expected, steps[index]);
+        }
+    }
+
+    #[test]
+    fn get_costs_matches_uniform_cost_search() {
+        for connectivity in [Connectivity::Four, Connectivity::EightWithoutCornerCutting] {
+            let graph = Graph::from_ascii(map())
+                .unwrap()
+                .graph
+                .with_connectivity(connectivity);
+            let nodes = graph.get_possible_nodes();
+            let start = Position::new(3, 5);
+            let costs = DistanceField::get_costs(&graph, &nodes, start).unwrap();
+
+            for (index, node) in nodes.iter().enumerate() {
+                let expected = if node.walkable {
+                    let problem = Problem::new(
+                        nodes.clone(),
+                        graph.get_node_id_at_position(start),
+                        NodeId::new(index),
+                        graph.size,
+                    )
+                    .unwrap();
+                    UniformCostSearch::search(&problem)
+                        .unwrap()
+                        .map(|result| result.cost)
+                } else {
+                    None
+                };
+                match (expected, costs[index]) {
+                    (Some(expected), Some(cost)) => assert!((expected - cost).abs() < 1e-9),
+                    (expected, cost) => assert_eq!(expected, cost),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn get_costs_to_goal_counts_tiles_towards_goal() {
+        let graph = Graph::new(vec![1, 0, 1], 3, 1).unwrap();
+        let nodes = graph.get_possible_nodes();
+
+        assert_eq!(
+            vec![Some(0.0), Some(1.0), Some(4.0)],
+            DistanceField::get_costs_to_goal(&graph, &nodes, Position::new(0, 0)).unwrap()
+        );
+        assert_eq!(
+            vec![Some(0.0), Some(3.0), Some(4.0)],
+            DistanceField::get_costs(&graph, &nodes, Position::new(0, 0)).unwrap()
+        );
+    }
+
+    #[test]
+    fn get_steps_with_invalid_start_returns_error() {
+        let graph = Graph::new(vec![1, 2], 2, 1).unwrap();
+        let nodes = graph.get_path_nodes();
+
+        assert_eq!(
+            Err(SearchError::StartNotWalkable(Position::new(0, 1))),
+            DistanceField::get_steps(&graph, &nodes, Position::new(0, 1))
+        );
+        assert_eq!(
+            Err(SearchError::PositionOutOfBounds(Position::new(1, 0))),
+            DistanceField::get_steps(&graph, &nodes, Position::new(1, 0))
+        );
+    }
+}
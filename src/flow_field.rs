@@ -0,0 +1,241 @@
+use crate::distance_field::DistanceField;
+use crate::graph::Graph;
+use crate::node::{Node, NodeId};
+use crate::position::Position;
+use crate::search_error::SearchError;
+
+#[derive(Debug, Clone)]
+/// The next step towards a shared goal for each tile
+///
+/// The field is computed once with a search from the goal, afterwards any number of agents
+/// follow it from their positions without a search of their own.
+///
+/// # Example
+///
+/// ```
+/// use rust_problem_search::flow_field::FlowField;
+/// use rust_problem_search::graph::Graph;
+/// use rust_problem_search::position::Position;
+///
+/// let graph = Graph::new(vec![1, 1, 1, 2, 2, 1], 3, 2).unwrap();
+/// let nodes = graph.get_path_nodes();
+/// let flow_field = FlowField::new(&graph, &nodes, Position::new(0, 0)).unwrap();
+///
+/// assert_eq!(Some((0, -1)), flow_field.get_direction(Position::new(0, 1)));
+/// assert_eq!(Some(3.0), flow_field.get_cost(Position::new(1, 2)));
+/// assert_eq!(4, flow_field.get_path(Position::new(1, 2)).unwrap().len());
+/// ```
+pub struct FlowField {
+    goal: Position,
+    next: Vec<Option<NodeId>>,
+    costs: Vec<Option<f64>>,
+    width: u32,
+    height: u32,
+}
+
+impl FlowField {
+    /// Returns the flow field towards the goal, or an error if the goal is invalid
+    ///
+    /// Each tile points to the neighbour on its cheapest path to the goal,
+    /// the costs are the ones of the uniform cost search.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - The graph the nodes are build from
+    /// * `nodes` - The nodes to move on, e.g. `graph.get_path_nodes()`
+    /// * `goal` - The position all paths lead to
+    pub fn new(graph: &Graph, nodes: &[Node], goal: Position) -> Result<Self, SearchError> {
+        let costs = DistanceField::get_costs_to_goal(graph, nodes, goal)?;
+        let goal_id = graph.get_node_id_at_position(goal);
+        let next = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| {
+                if index == goal_id.index() {
+                    return None;
+                }
+                costs[index]?;
+                node.neighbours
+                    .iter()
+                    .filter_map(|next| {
+                        let step_cost = nodes[next.index()].cost_from(node)?;
+                        Some((*next, step_cost + costs[next.index()]?))
+                    })
+                    .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                    .map(|(next, _)| next)
+            })
+            .collect();
+        Ok(FlowField {
+            goal,
+            next,
+            costs,
+            width: graph.width,
+            height: graph.height,
+        })
+    }
+
+    /// Returns the goal of the flow field
+    pub fn get_goal(&self) -> Position {
+        self.goal
+    }
+
+    /// Returns the cost of the cheapest path from the position to the goal,
+    /// None if there is no path or the position is outside of the graph
+    pub fn get_cost(&self, position: Position) -> Option<f64> {
+        self.costs[self.get_index(position)?]
+    }
+
+    /// Returns the next position on the way to the goal,
+    /// None at the goal, if there is no path or the position is outside of the graph
+    pub fn get_next(&self, position: Position) -> Option<Position> {
+        self.next[self.get_index(position)?].map(|id| self.get_position(id))
+    }
+
+    /// Returns the step to the next position as row and column offset
+    /// like the offsets of `Connectivity`, None where `get_next` is None
+    pub fn get_direction(&self, position: Position) -> Option<(i32, i32)> {
+        self.get_next(position).map(|next| {
+            (
+                next.row as i32 - position.row as i32,
+                next.column as i32 - position.column as i32,
+            )
+        })
+    }
+
+    /// Returns the positions from the position to the goal by following the field,
+    /// None if there is no path or the position is outside of the graph
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The position to start from
+    pub fn get_path(&self, position: Position) -> Option<Vec<Position>> {
+        self.get_cost(position)?;
+        let mut path = vec![position];
+        let mut current = position;
+        while let Some(next) = self.get_next(current) {
+            path.push(next);
+            current = next;
+        }
+        Some(path)
+    }
+
+    fn get_index(&self, position: Position) -> Option<usize> {
+        if position.row < self.height && position.column < self.width {
+            Some((position.row * self.width + position.column) as usize)
+        } else {
+            None
+        }
+    }
+
+    fn get_position(&self, id: NodeId) -> Position {
+        let index = id.index() as u32;
+        Position::new(index / self.width, index % self.width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectivity::Connectivity;
+    use crate::problem::Problem;
+    use crate::uniform_cost_search::UniformCostSearch;
+
+    fn map() -> &'static str {
+        "
+.... .
+.##. #
+.  . .
+##.#..
+"
+    }
+
+    #[test]
+    fn get_path_returns_cheapest_path_from_each_tile() {
+        for connectivity in [
+            Connectivity::Four,
+            Connectivity::Eight,
+            Connectivity::EightWithoutCornerCutting,
+        ] {
+            let graph = Graph::from_ascii(map())
+                .unwrap()
+                .graph
+                .with_connectivity(connectivity);
+            let nodes = graph.get_possible_nodes();
+            let goal = Position::new(0, 0);
+            let flow_field = FlowField::new(&graph, &nodes, goal).unwrap();
+
+            for node in nodes.iter().filter(|node| node.walkable) {
+                let problem = Problem::new(
+                    nodes.clone(),
+                    graph.get_node_id_at_position(node.position),
+                    graph.get_node_id_at_position(goal),
+                    graph.size,
+                )
+                .unwrap();
+                let expected = UniformCostSearch::search(&problem)
+                    .unwrap()
+                    .map(|result| result.cost);
+                let path = flow_field.get_path(node.position);
+                assert_eq!(expected.is_some(), path.is_some());
+
+                if let (Some(expected), Some(path)) = (expected, path) {
+                    let cost: f64 = path
+                        .windows(2)
+                        .map(|step| {
+                            let from = &nodes[graph.get_index_at_position(step[0])];
+                            let to = &nodes[graph.get_index_at_position(step[1])];
+                            assert!(from
+                                .neighbours
+                                .contains(&graph.get_node_id_at_position(to.position)));
+                            to.cost_from(from).unwrap()
+                        })
+                        .sum();
+                    assert!((expected - cost).abs() < 1e-9);
+                    assert!((expected - flow_field.get_cost(node.position).unwrap()).abs() < 1e-9);
+                    assert_eq!(goal, path[path.len() - 1]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn get_next_at_goal_returns_none() {
+        let graph = Graph::from_ascii(map()).unwrap().graph;
+        let flow_field =
+            FlowField::new(&graph, &graph.get_path_nodes(), Position::new(2, 0)).unwrap();
+
+        assert_eq!(Position::new(2, 0), flow_field.get_goal());
+        assert_eq!(None, flow_field.get_next(Position::new(2, 0)));
+        assert_eq!(
+            Some(Position::new(2, 0)),
+            flow_field.get_next(Position::new(1, 0))
+        );
+        assert_eq!(Some((1, 0)), flow_field.get_direction(Position::new(1, 0)));
+        assert_eq!(
+            Some(vec![Position::new(2, 0)]),
+            flow_field.get_path(Position::new(2, 0))
+        );
+    }
+
+    #[test]
+    fn get_path_without_path_returns_none() {
+        let graph = Graph::from_ascii(map()).unwrap().graph;
+        let flow_field =
+            FlowField::new(&graph, &graph.get_path_nodes(), Position::new(0, 0)).unwrap();
+
+        assert_eq!(None, flow_field.get_path(Position::new(3, 5)));
+        assert_eq!(None, flow_field.get_path(Position::new(1, 1)));
+        assert_eq!(None, flow_field.get_path(Position::new(4, 0)));
+        assert_eq!(None, flow_field.get_direction(Position::new(3, 5)));
+    }
+
+    #[test]
+    fn new_with_goal_on_ground_returns_error() {
+        let graph = Graph::from_ascii(map()).unwrap().graph;
+
+        assert_eq!(
+            SearchError::GoalNotWalkable(Position::new(1, 1)),
+            FlowField::new(&graph, &graph.get_path_nodes(), Position::new(1, 1)).unwrap_err()
+        );
+    }
+}
@@ -1,7 +1,11 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
+use std::convert::TryFrom;
 
-use crate::node::Node;
+use crate::components::Components;
+use crate::connectivity::Connectivity;
+use crate::node::{Node, NodeId};
 use crate::position::Position;
+use crate::search_error::SearchError;
 use crate::tile::Tile;
 
 #[derive(Debug, Clone)]
@@ -11,6 +15,7 @@ pub struct Graph {
     pub width: u32,
     pub height: u32,
     pub size: usize,
+    pub connectivity: Connectivity,
 }
 
 impl Graph {
@@ -21,61 +26,279 @@ impl Graph {
     /// * `tiles` - A Vec<u8> with 1 for path, 2 for ground and 0 for empty tile
     /// * `width` - The graphs width
     /// * `height` - The graphs height
-    pub fn new(tiles: Vec<u8>, width: u32, height: u32) -> Self {
-        let size: usize = (width * height) as usize;
-        Graph {
+    ///
+    /// The graph is four-connected, see `with_connectivity`.
+    /// Returns an error if the number of tiles is not width * height
+    /// or width * height does not fit into an u32, the type of a NodeId
+    pub fn new(tiles: Vec<u8>, width: u32, height: u32) -> Result<Self, SearchError> {
+        let size = match width.checked_mul(height) {
+            Some(size) if tiles.len() == size as usize => size as usize,
+            _ => {
+                return Err(SearchError::InvalidTileCount {
+                    expected: usize::try_from(u64::from(width) * u64::from(height))
+                        .unwrap_or(usize::MAX),
+                    actual: tiles.len(),
+                })
+            }
+        };
+        Ok(Graph {
             tiles,
             width,
             height,
             size,
-        }
+            connectivity: Connectivity::Four,
+        })
     }
 
-    /// Returns a HashMap, where the key is the position
-    /// and the value is a node
+    /// Returns the graph with the given connectivity
+    ///
+    /// # Arguments
+    ///
+    /// * `connectivity` - Defines which tiles around a tile are its neighbours
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::rust_problem_search::connectivity::Connectivity;
+    /// use crate::rust_problem_search::graph::Graph;
+    ///
+    /// let graph = Graph::new(vec![1; 9], 3, 3)
+    ///     .unwrap()
+    ///     .with_connectivity(Connectivity::Eight);
+    ///
+    /// assert_eq!(8, graph.get_path_nodes()[4].neighbours.len());
+    /// ```
+    pub fn with_connectivity(mut self, connectivity: Connectivity) -> Self {
+        self.connectivity = connectivity;
+        self
+    }
+
+    /// Returns a Vec with a node for each tile, indexed by NodeId
     /// and the node neighbours are path tiles
-    pub fn get_path_nodes(&self) -> HashMap<String, Node> {
+    pub fn get_path_nodes(&self) -> Vec<Node> {
         let mut tiles = HashSet::new();
         tiles.insert(Tile::Path);
         self.get_neighbours_with_tile(&tiles)
     }
 
-    /// Returns a HashMap, where the key is the position
-    /// and the value is a node
+    /// Returns a Vec with a node for each tile, indexed by NodeId
     /// and the node neighbours are path or empty tiles
     /// and the node tile is a path or empty tile
-    pub fn get_possible_nodes(&self) -> HashMap<String, Node> {
+    pub fn get_possible_nodes(&self) -> Vec<Node> {
         let mut tiles = HashSet::new();
         tiles.insert(Tile::Path);
         tiles.insert(Tile::None);
         self.get_neighbours_with_tile(&tiles)
     }
 
+    /// Returns the connected regions of the path tiles,
+    /// the regions of the nodes of `get_path_nodes`
+    pub fn get_path_components(&self) -> Components {
+        Components::new(self, &self.get_path_nodes())
+    }
+
+    /// Returns the connected regions of the path and empty tiles,
+    /// the regions of the nodes of `get_possible_nodes`
+    pub fn get_possible_components(&self) -> Components {
+        Components::new(self, &self.get_possible_nodes())
+    }
+
     /// Returns the index in the tile vec
     /// for a given position
     pub fn get_index_at_position(&self, position: Position) -> usize {
         (position.row * self.width + position.column) as usize
     }
 
-    fn get_neighbours_with_tile(
+    /// Returns the node id
+    /// for a given position
+    pub fn get_node_id_at_position(&self, position: Position) -> NodeId {
+        NodeId::new(self.get_index_at_position(position))
+    }
+
+    /// Returns the position
+    /// for a given node id
+    pub fn get_position_at_node_id(&self, id: NodeId) -> Position {
+        self.get_position_at_index(id.index())
+    }
+
+    /// Returns true if the position is inside of the graph
+    pub fn contains(&self, position: Position) -> bool {
+        position.row < self.height && position.column < self.width
+    }
+
+    /// Sets the tile at a position
+    ///
+    /// Nodes returned before keep their neighbours, see `MutableGraph` to keep them in sync.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The position of the tile
+    /// * `tile` - The new tile
+    ///
+    /// Returns an error if the position is outside of the graph
+    pub fn set_tile(&mut self, position: Position, tile: Tile) -> Result<(), SearchError> {
+        self.set_tiles(&[(position, tile)])
+    }
+
+    /// Sets several tiles at once, none of them if a position is outside of the graph
+    ///
+    /// # Arguments
+    ///
+    /// * `tiles` - The positions with their new tiles
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::rust_problem_search::graph::Graph;
+    /// use crate::rust_problem_search::position::Position;
+    /// use crate::rust_problem_search::tile::Tile;
+    ///
+    /// let mut graph = Graph::new(vec![1; 4], 2, 2).unwrap();
+    /// graph
+    ///     .set_tiles(&[(Position::new(0, 1), Tile::Ground), (Position::new(1, 1), Tile::None)])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(vec![1, 2, 1, 0], graph.tiles);
+    /// ```
+    pub fn set_tiles(&mut self, tiles: &[(Position, Tile)]) -> Result<(), SearchError> {
+        if let Some((position, _)) = tiles.iter().find(|(position, _)| !self.contains(*position)) {
+            return Err(SearchError::PositionOutOfBounds(*position));
+        }
+        for (position, tile) in tiles {
+            let index = self.get_index_at_position(*position);
+            self.tiles[index] = *tile as u8;
+        }
+        Ok(())
+    }
+
+    /// Returns the cost to traverse the tile at a given position,
+    /// or None if the position is outside of the graph or the tile is never walkable
+    pub fn get_cost_at_position(&self, position: Position) -> Option<u32> {
+        if !self.contains(position) {
+            return None;
+        }
+        self.get_value_at_position(position)
+            .and_then(|tile| tile.cost())
+    }
+
+    /// Returns true if the straight line between the centres of two positions
+    /// only crosses walkable nodes, a line through a corner needs both tiles at the corner
+    ///
+    /// # Arguments
+    ///
+    /// * `nodes` - The nodes of the graph, e.g. from `get_path_nodes`
+    /// * `from` - The position where the line starts
+    /// * `to` - The position where the line ends
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::rust_problem_search::graph::Graph;
+    /// use crate::rust_problem_search::position::Position;
+    ///
+    /// let graph = Graph::new(vec![1, 1, 1, 1, 2, 1], 3, 2).unwrap();
+    /// let nodes = graph.get_path_nodes();
+    ///
+    /// assert!(graph.has_line_of_sight(&nodes, Position::new(0, 0), Position::new(0, 2)));
+    /// assert!(!graph.has_line_of_sight(&nodes, Position::new(0, 0), Position::new(1, 2)));
+    /// ```
+    pub fn has_line_of_sight(&self, nodes: &[Node], from: Position, to: Position) -> bool {
+        let is_walkable = |row: i64, column: i64| {
+            self.get_position(row as i32, column as i32)
+                .and_then(|position| nodes.get(self.get_index_at_position(position)))
+                .is_some_and(|node| node.walkable)
+        };
+        let (rows, columns) = (
+            i64::from(to.row) - i64::from(from.row),
+            i64::from(to.column) - i64::from(from.column),
+        );
+        let (row_step, column_step) = (rows.signum(), columns.signum());
+        let (rows, columns) = (rows.abs(), columns.abs());
+        let (mut row, mut column) = (i64::from(from.row), i64::from(from.column));
+        let (mut crossed_rows, mut crossed_columns) = (0, 0);
+
+        if !is_walkable(row, column) {
+            return false;
+        }
+        while crossed_rows < rows || crossed_columns < columns {
+            // compares where the line crosses the next row and the next column border
+            let next = (1 + 2 * crossed_rows) * columns - (1 + 2 * crossed_columns) * rows;
+            if next == 0 {
+                if !is_walkable(row + row_step, column) || !is_walkable(row, column + column_step) {
+                    return false;
+                }
+                row += row_step;
+                column += column_step;
+                crossed_rows += 1;
+                crossed_columns += 1;
+            } else if next < 0 {
+                row += row_step;
+                crossed_rows += 1;
+            } else {
+                column += column_step;
+                crossed_columns += 1;
+            }
+            if !is_walkable(row, column) {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub(crate) fn get_neighbours_with_tile(
         &self,
         tiles: &HashSet<Tile>,
-    ) -> HashMap<String, Node> {
-        let mut nodes = HashMap::new();
-        for (index, value) in self.tiles.iter().enumerate() {
-            let position = self.get_position_at_index(index);
-            let neighbours = if tiles.contains(&Tile::from_u8(*value)) {
-                self.get_neighbours_at_position(position, tiles)
-            } else {
-                vec![]
-            };
-            let neighbours = neighbours.iter().map(|p| format!("{}", p)).collect();
-            nodes.insert(
-                format!("{}", position),
-                Node::new(position.clone(), Tile::from_u8(*value), neighbours),
-            );
+    ) -> Vec<Node> {
+        (0..self.tiles.len())
+            .map(|index| self.get_node_with_tile(self.get_position_at_index(index), tiles))
+            .collect()
+    }
+
+    /// Rebuilds the nodes whose neighbours may have changed with the tiles at the positions
+    /// and returns their indices
+    ///
+    /// A tile changes if it is walkable and so the neighbours of the tiles around it,
+    /// including the diagonal moves past it without corner cutting. The nodes further away
+    /// stay the same.
+    pub(crate) fn update_nodes_with_tile(
+        &self,
+        nodes: &mut [Node],
+        positions: &[Position],
+        tiles: &HashSet<Tile>,
+    ) -> Vec<usize> {
+        let mut indices: Vec<usize> = positions
+            .iter()
+            .flat_map(|position| {
+                let (row, column) = (position.row as i32, position.column as i32);
+                (row - 1..=row + 1)
+                    .flat_map(move |row| (column - 1..=column + 1).map(move |column| (row, column)))
+            })
+            .filter_map(|(row, column)| self.get_position(row, column))
+            .map(|position| self.get_index_at_position(position))
+            .collect();
+        indices.sort_unstable();
+        indices.dedup();
+        for index in &indices {
+            nodes[*index] = self.get_node_with_tile(self.get_position_at_index(*index), tiles);
         }
-        nodes
+        indices
+    }
+
+    /// Returns the node at a position, which is walkable and has neighbours
+    /// if its tile is one of the given tiles
+    pub(crate) fn get_node_with_tile(&self, position: Position, tiles: &HashSet<Tile>) -> Node {
+        let value = Tile::from_u8(self.tiles[self.get_index_at_position(position)]);
+        let walkable = tiles.contains(&value);
+        let neighbours = if walkable {
+            self.get_neighbours_at_position(position, tiles)
+        } else {
+            vec![]
+        };
+        let neighbours = neighbours
+            .iter()
+            .map(|p| self.get_node_id_at_position(*p))
+            .collect();
+        Node::new(position, value, walkable, neighbours)
     }
 
     fn get_neighbours_at_position(
@@ -84,34 +307,40 @@ impl Graph {
         tiles: &HashSet<Tile>,
     ) -> Vec<Position> {
         let mut result = vec![];
-        let rows = vec![
-            position.row as i32 - 1,
-            position.row as i32,
-            position.row as i32 + 1,
-            position.row as i32,
-        ];
-        let columns = vec![
-            position.column as i32,
-            position.column as i32 + 1,
-            position.column as i32,
-            position.column as i32 - 1,
-        ];
-        let positions = rows.iter().zip(columns.iter());
-
-        for (row, column) in positions {
-            if *row >= 0 && *column >= 0 && *row < self.height as i32 && *column < self.width as i32
+        let is_walkable = |row: i32, column: i32| {
+            self.get_position(row, column)
+                .and_then(|neighbour_position| self.get_value_at_position(neighbour_position))
+                .is_some_and(|value| tiles.contains(&value))
+        };
+
+        for (row_offset, column_offset) in self.connectivity.offsets() {
+            let row = position.row as i32 + row_offset;
+            let column = position.column as i32 + column_offset;
+            let diagonal = *row_offset != 0 && *column_offset != 0;
+
+            if !is_walkable(row, column) {
+                continue;
+            }
+            if diagonal
+                && self.connectivity == Connectivity::EightWithoutCornerCutting
+                && !(is_walkable(row, position.column as i32)
+                    && is_walkable(position.row as i32, column))
             {
-                let neighbour_position = Position::new(*row as u32, *column as u32);
-                if let Some(value) = self.get_value_at_position(neighbour_position) {
-                    if tiles.contains(&value) {
-                        result.push(neighbour_position);
-                    }
-                }
+                continue;
             }
+            result.push(Position::new(row as u32, column as u32));
         }
         result
     }
 
+    fn get_position(&self, row: i32, column: i32) -> Option<Position> {
+        if row >= 0 && column >= 0 && row < self.height as i32 && column < self.width as i32 {
+            Some(Position::new(row as u32, column as u32))
+        } else {
+            None
+        }
+    }
+
     fn get_position_at_index(&self, index: usize) -> Position {
         let row = index as u32 / self.width;
         let column = index as u32 - (self.width * row);
@@ -139,143 +368,210 @@ mod tests {
 
     #[test]
     fn new_returns_new_graph() {
-        let tiles: Vec<u8> = vec![2, 1, 2, 2, 1, 1, 2, 1, 2];
-        let graph = Graph::new(tiles, 18, 18);
+        let tiles: Vec<u8> = vec![1; 324];
+        let graph = Graph::new(tiles, 18, 18).unwrap();
         assert_eq!(18, graph.width);
         assert_eq!(18, graph.height);
         assert_eq!(324, graph.size);
+        assert_eq!(Connectivity::Four, graph.connectivity);
+    }
+
+    #[test]
+    fn new_with_invalid_tile_count_returns_error() {
+        let tiles: Vec<u8> = vec![2, 1, 2, 2, 1, 1, 2, 1, 2];
+        assert_eq!(
+            Err(SearchError::InvalidTileCount {
+                expected: 324,
+                actual: 9
+            }),
+            Graph::new(tiles, 18, 18).map(|graph| graph.size)
+        );
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn new_with_overflowing_size_returns_error() {
+        assert_eq!(
+            Err(SearchError::InvalidTileCount {
+                expected: 1 << 32,
+                actual: 0
+            }),
+            Graph::new(vec![], 65536, 65536).map(|graph| graph.size)
+        );
     }
 
     #[test]
     fn get_path_nodes_returns_nodes() {
         let tiles: Vec<u8> = vec![2, 1, 2, 2, 1, 1, 2, 1, 2];
-        let graph = Graph::new(tiles, 3, 3);
+        let graph = Graph::new(tiles, 3, 3).unwrap();
         let nodes = graph.get_path_nodes();
 
         assert_eq!(
-            Node::new(Position::new(0, 0), Tile::Ground, vec![]),
-            nodes["0-0"]
+            Node::new(Position::new(0, 0), Tile::Ground, false, vec![]),
+            nodes[0]
         );
         assert_eq!(
-            Node::new(Position::new(0, 1), Tile::Path, vec!["1-1".to_string()]),
-            nodes["0-1"]
+            Node::new(Position::new(0, 1), Tile::Path, true, vec![NodeId(4)]),
+            nodes[1]
         );
         assert_eq!(
-            Node::new(Position::new(0, 2), Tile::Ground, vec![]),
-            nodes["0-2"]
+            Node::new(Position::new(0, 2), Tile::Ground, false, vec![]),
+            nodes[2]
         );
 
         assert_eq!(
-            Node::new(Position::new(1, 0), Tile::Ground, vec![]),
-            nodes["1-0"]
+            Node::new(Position::new(1, 0), Tile::Ground, false, vec![]),
+            nodes[3]
         );
         assert_eq!(
             Node::new(
                 Position::new(1, 1),
                 Tile::Path,
-                vec!["0-1".to_string(), "1-2".to_string(), "2-1".to_string()],
+                true,
+                vec![NodeId(1), NodeId(5), NodeId(7)],
             ),
-            nodes["1-1"]
+            nodes[4]
         );
         assert_eq!(
-            Node::new(Position::new(1, 2), Tile::Path, vec!["1-1".to_string()]),
-            nodes["1-2"]
+            Node::new(Position::new(1, 2), Tile::Path, true, vec![NodeId(4)]),
+            nodes[5]
         );
 
         assert_eq!(
-            Node::new(Position::new(2, 0), Tile::Ground, vec![]),
-            nodes["2-0"]
+            Node::new(Position::new(2, 0), Tile::Ground, false, vec![]),
+            nodes[6]
         );
         assert_eq!(
-            Node::new(Position::new(2, 1), Tile::Path, vec!["1-1".to_string()]),
-            nodes["2-1"]
+            Node::new(Position::new(2, 1), Tile::Path, true, vec![NodeId(4)]),
+            nodes[7]
         );
         assert_eq!(
-            Node::new(Position::new(2, 2), Tile::Ground, vec![]),
-            nodes["2-2"]
+            Node::new(Position::new(2, 2), Tile::Ground, false, vec![]),
+            nodes[8]
         );
     }
 
     #[test]
     fn get_possible_nodes_returns_nodes() {
         let tiles: Vec<u8> = vec![2, 1, 2, 0, 2, 1, 1, 0, 2, 1, 2, 0, 0, 0, 0, 0];
-        let graph = Graph::new(tiles, 4, 4);
+        let graph = Graph::new(tiles, 4, 4).unwrap();
         let nodes = graph.get_possible_nodes();
 
         assert_eq!(
             Node::new(
                 Position::new(2, 1),
                 Tile::Path,
-                vec!["1-1".to_string(), "3-1".to_string()],
+                true,
+                vec![NodeId(5), NodeId(13)],
             ),
-            nodes["2-1"]
+            nodes[9]
         );
         assert_eq!(
             Node::new(
                 Position::new(1, 2),
                 Tile::Path,
-                vec!["1-3".to_string(), "1-1".to_string()],
+                true,
+                vec![NodeId(7), NodeId(5)],
             ),
-            nodes["1-2"]
+            nodes[6]
         );
     }
 
     #[test]
     fn get_index_at_position_returns_index() {
-        let graph = Graph::new(vec![], 3, 3);
+        let graph = Graph::new(vec![1; 9], 3, 3).unwrap();
         assert_eq!(4, graph.get_index_at_position(Position::new(1, 1)));
     }
 
+    #[test]
+    fn contains_returns_true_for_positions_inside() {
+        let graph = Graph::new(vec![1; 6], 3, 2).unwrap();
+
+        assert!(graph.contains(Position::new(1, 2)));
+        assert!(!graph.contains(Position::new(2, 0)));
+        assert!(!graph.contains(Position::new(0, 3)));
+    }
+
+    #[test]
+    fn get_cost_at_position_returns_cost() {
+        let tiles: Vec<u8> = vec![2, 1, 0, 2];
+        let graph = Graph::new(tiles, 2, 2).unwrap();
+
+        assert_eq!(None, graph.get_cost_at_position(Position::new(0, 0)));
+        assert_eq!(Some(1), graph.get_cost_at_position(Position::new(0, 1)));
+        assert_eq!(Some(3), graph.get_cost_at_position(Position::new(1, 0)));
+        assert_eq!(None, graph.get_cost_at_position(Position::new(0, 2)));
+    }
+
+    #[test]
+    fn get_node_id_at_position_returns_node_id() {
+        let graph = Graph::new(vec![1; 9], 3, 3).unwrap();
+        assert_eq!(
+            NodeId(5),
+            graph.get_node_id_at_position(Position::new(1, 2))
+        );
+    }
+
+    #[test]
+    fn get_position_at_node_id_returns_position() {
+        let graph = Graph::new(vec![1; 9], 3, 3).unwrap();
+        assert_eq!(
+            Position::new(1, 2),
+            graph.get_position_at_node_id(NodeId(5))
+        );
+    }
+
     #[test]
     fn get_neighbours_with_tile_with_tile_returns_nodes() {
         let mut tile_types = HashSet::new();
         tile_types.insert(Tile::Path);
 
         let tiles: Vec<u8> = vec![2, 1, 2, 2, 1, 1, 2, 1, 2];
-        let graph = Graph::new(tiles, 3, 3);
+        let graph = Graph::new(tiles, 3, 3).unwrap();
         let nodes = graph.get_neighbours_with_tile(&tile_types);
 
         assert_eq!(
-            Node::new(Position::new(0, 0), Tile::Ground, vec![]),
-            nodes["0-0"]
+            Node::new(Position::new(0, 0), Tile::Ground, false, vec![]),
+            nodes[0]
         );
         assert_eq!(
-            Node::new(Position::new(0, 1), Tile::Path, vec!["1-1".to_string()]),
-            nodes["0-1"]
+            Node::new(Position::new(0, 1), Tile::Path, true, vec![NodeId(4)]),
+            nodes[1]
         );
         assert_eq!(
-            Node::new(Position::new(0, 2), Tile::Ground, vec![]),
-            nodes["0-2"]
+            Node::new(Position::new(0, 2), Tile::Ground, false, vec![]),
+            nodes[2]
         );
 
         assert_eq!(
-            Node::new(Position::new(1, 0), Tile::Ground, vec![]),
-            nodes["1-0"]
+            Node::new(Position::new(1, 0), Tile::Ground, false, vec![]),
+            nodes[3]
         );
         assert_eq!(
             Node::new(
                 Position::new(1, 1),
                 Tile::Path,
-                vec!["0-1".to_string(), "1-2".to_string(), "2-1".to_string()],
+                true,
+                vec![NodeId(1), NodeId(5), NodeId(7)],
             ),
-            nodes["1-1"]
+            nodes[4]
         );
         assert_eq!(
-            Node::new(Position::new(1, 2), Tile::Path, vec!["1-1".to_string()]),
-            nodes["1-2"]
+            Node::new(Position::new(1, 2), Tile::Path, true, vec![NodeId(4)]),
+            nodes[5]
         );
 
         assert_eq!(
-            Node::new(Position::new(2, 0), Tile::Ground, vec![]),
-            nodes["2-0"]
+            Node::new(Position::new(2, 0), Tile::Ground, false, vec![]),
+            nodes[6]
         );
         assert_eq!(
-            Node::new(Position::new(2, 1), Tile::Path, vec!["1-1".to_string()]),
-            nodes["2-1"]
+            Node::new(Position::new(2, 1), Tile::Path, true, vec![NodeId(4)]),
+            nodes[7]
         );
         assert_eq!(
-            Node::new(Position::new(2, 2), Tile::Ground, vec![]),
-            nodes["2-2"]
+            Node::new(Position::new(2, 2), Tile::Ground, false, vec![]),
+            nodes[8]
         );
     }
 
@@ -285,35 +581,108 @@ mod tests {
         tile_types.insert(Tile::Path);
 
         let tiles: Vec<u8> = vec![2, 1, 2, 2, 1, 1, 2, 1, 2];
-        let graph = Graph::new(tiles, 3, 3);
+        let graph = Graph::new(tiles, 3, 3).unwrap();
         let nodes = graph.get_neighbours_at_position(Position::new(1, 1), &tile_types);
 
         assert_eq!(3, nodes.len());
-        assert_eq!(Some(&Position::new(0, 1)), nodes.get(0));
+        assert_eq!(Some(&Position::new(0, 1)), nodes.first());
         assert_eq!(Some(&Position::new(1, 2)), nodes.get(1));
         assert_eq!(Some(&Position::new(2, 1)), nodes.get(2));
     }
 
+    #[test]
+    fn get_neighbours_at_position_with_eight_returns_diagonal_neighbours() {
+        let mut tile_types = HashSet::new();
+        tile_types.insert(Tile::Path);
+
+        let tiles: Vec<u8> = vec![
+            1, 2, 1,
+            2, 1, 1,
+            1, 1, 2];
+        let graph = Graph::new(tiles, 3, 3)
+            .unwrap()
+            .with_connectivity(Connectivity::Eight);
+        let nodes = graph.get_neighbours_at_position(Position::new(1, 1), &tile_types);
+
+        assert_eq!(
+            vec![
+                Position::new(1, 2),
+                Position::new(2, 1),
+                Position::new(0, 2),
+                Position::new(2, 0),
+                Position::new(0, 0),
+            ],
+            nodes
+        );
+    }
+
+    #[test]
+    fn get_neighbours_at_position_without_corner_cutting_skips_blocked_diagonals() {
+        let mut tile_types = HashSet::new();
+        tile_types.insert(Tile::Path);
+
+        let tiles: Vec<u8> = vec![
+            1, 2, 1,
+            2, 1, 1,
+            1, 1, 2];
+        let graph = Graph::new(tiles, 3, 3)
+            .unwrap()
+            .with_connectivity(Connectivity::EightWithoutCornerCutting);
+        let nodes = graph.get_neighbours_at_position(Position::new(1, 1), &tile_types);
+
+        assert_eq!(vec![Position::new(1, 2), Position::new(2, 1)], nodes);
+    }
+
+    #[test]
+    fn has_line_of_sight_checks_crossed_tiles() {
+        let tiles: Vec<u8> = vec![
+            1, 1, 1, 1,
+            1, 2, 1, 1,
+            1, 1, 1, 1];
+        let graph = Graph::new(tiles, 4, 3).unwrap();
+        let nodes = graph.get_path_nodes();
+
+        assert!(graph.has_line_of_sight(&nodes, Position::new(0, 0), Position::new(0, 3)));
+        assert!(graph.has_line_of_sight(&nodes, Position::new(0, 0), Position::new(2, 0)));
+        assert!(!graph.has_line_of_sight(&nodes, Position::new(2, 0), Position::new(0, 3)));
+        assert!(!graph.has_line_of_sight(&nodes, Position::new(0, 0), Position::new(2, 2)));
+        assert!(!graph.has_line_of_sight(&nodes, Position::new(0, 2), Position::new(1, 0)));
+        assert!(graph.has_line_of_sight(&nodes, Position::new(0, 2), Position::new(2, 3)));
+        assert!(!graph.has_line_of_sight(&nodes, Position::new(1, 1), Position::new(1, 1)));
+    }
+
+    #[test]
+    fn has_line_of_sight_through_corner_needs_both_tiles() {
+        let tiles: Vec<u8> = vec![
+            1, 2,
+            1, 1];
+        let graph = Graph::new(tiles, 2, 2).unwrap();
+        let nodes = graph.get_path_nodes();
+
+        assert!(!graph.has_line_of_sight(&nodes, Position::new(0, 0), Position::new(1, 1)));
+        assert!(graph.has_line_of_sight(&nodes, Position::new(1, 0), Position::new(1, 1)));
+    }
+
     #[test]
     fn get_position_at_index_returns_position() {
-        let graph = Graph::new(vec![], 3, 3);
-        assert_eq!(Position::new(1, 1), graph.get_position_at_index(4 as usize));
+        let graph = Graph::new(vec![1; 9], 3, 3).unwrap();
+        assert_eq!(Position::new(1, 1), graph.get_position_at_index(4));
     }
 
     #[test]
     fn get_value_at_index_returns_value() {
         let tiles: Vec<u8> = vec![2, 1, 2, 2, 1, 1, 2, 1, 2];
-        let graph = Graph::new(tiles, 3, 3);
+        let graph = Graph::new(tiles, 3, 3).unwrap();
 
-        assert_eq!(Some(Tile::Ground), graph.get_value_at_index(2 as usize));
-        assert_eq!(Some(Tile::Path), graph.get_value_at_index(4 as usize));
-        assert_eq!(None, graph.get_value_at_index(10 as usize));
+        assert_eq!(Some(Tile::Ground), graph.get_value_at_index(2));
+        assert_eq!(Some(Tile::Path), graph.get_value_at_index(4));
+        assert_eq!(None, graph.get_value_at_index(10));
     }
 
     #[test]
     fn get_value_at_position_returns_value() {
         let tiles: Vec<u8> = vec![2, 1, 2, 2, 1, 1, 2, 1, 2];
-        let graph = Graph::new(tiles, 3, 3);
+        let graph = Graph::new(tiles, 3, 3).unwrap();
 
         assert_eq!(
             Some(Tile::Ground),
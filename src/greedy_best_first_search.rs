@@ -0,0 +1,109 @@
+use crate::a_star_search::AStarSearch;
+use crate::problem::Problem;
+use crate::search_error::SearchError;
+use crate::search_problem::SearchProblem;
+use crate::search_result::BoundedSearchResult;
+
+#[derive(Debug, Clone)]
+pub struct GreedyBestFirstSearch {}
+
+impl GreedyBestFirstSearch {
+    /// Returns an Option with a path, its suboptimality bound and the search statistics,
+    /// or an error if the problem is invalid
+    ///
+    /// The node closest to the goal by the heuristic of `AStarSearch` is expanded first,
+    /// the cost to reach it is ignored. The search usually expands the fewest nodes,
+    /// but the path has no guaranteed cost, so the bound is infinite.
+    ///
+    /// # Arguments
+    ///
+    /// * `problem` The problem contains the start, the end and the nodes where to find the path
+    ///
+    /// # Example
+    /// ```
+    /// use rust_problem_search::graph::Graph;
+    /// use rust_problem_search::greedy_best_first_search::GreedyBestFirstSearch;
+    ///
+    /// let ascii_map = Graph::from_ascii("S...\n.##.\n...G\n").unwrap();
+    /// let problem = ascii_map.get_path_problem().unwrap();
+    ///
+    /// let bounded_result = GreedyBestFirstSearch::search(&problem).unwrap().unwrap();
+    ///
+    /// assert_eq!(5, bounded_result.result.length);
+    /// assert_eq!(f64::INFINITY, bounded_result.bound);
+    /// ```
+    pub fn search(problem: &Problem) -> Result<Option<BoundedSearchResult>, SearchError> {
+        problem.validate()?;
+
+        let heuristic = AStarSearch::get_heuristic(problem);
+        Ok(
+            AStarSearch::search_with_estimate(problem, |id, _| heuristic(id)).map(|result| {
+                BoundedSearchResult {
+                    result: result.map(|id| problem.get_position(id)),
+                    bound: f64::INFINITY,
+                }
+            }),
+        )
+    }
+
+    /// Returns an Option with a path of states to a goal, its suboptimality bound
+    /// and the search statistics, guided by the heuristic of the problem only
+    ///
+    /// # Arguments
+    ///
+    /// * `problem` Any problem with an initial state, a goal test, successors and a heuristic
+    pub fn search_problem<P: SearchProblem>(problem: &P) -> Option<BoundedSearchResult<P::State>> {
+        AStarSearch::search_with_estimate(problem, |state, _| problem.heuristic(state)).map(
+            |result| BoundedSearchResult {
+                result,
+                bound: f64::INFINITY,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Graph;
+    use crate::weighted_a_star_search::WeightedAStarSearch;
+
+    #[test]
+    fn search_expands_fewer_nodes_than_weighted_a_star_search() {
+        let map = "
+....................
+.S.......#..........
+.........#..........
+.........#.....G....
+.........#..........
+.....#####..........
+....................
+";
+        let problem = Graph::from_ascii(map).unwrap().get_path_problem().unwrap();
+        let greedy_result = GreedyBestFirstSearch::search(&problem).unwrap().unwrap();
+        let weighted_result = WeightedAStarSearch::search(&problem, 1.5).unwrap().unwrap();
+
+        assert_eq!(f64::INFINITY, greedy_result.bound);
+        assert!(greedy_result.result.expanded < weighted_result.result.expanded);
+    }
+
+    #[test]
+    fn search_prefers_closer_nodes_over_cheaper_ones() {
+        let ascii_map = Graph::from_ascii("S   G\n.....\n").unwrap();
+        let problem = ascii_map.get_possible_problem().unwrap();
+        let bounded_result = GreedyBestFirstSearch::search(&problem).unwrap().unwrap();
+
+        assert_eq!(4, bounded_result.result.length);
+        assert_eq!(10.0, bounded_result.result.cost);
+    }
+
+    #[test]
+    fn search_without_path_returns_none() {
+        let problem = Graph::from_ascii("S#G")
+            .unwrap()
+            .get_path_problem()
+            .unwrap();
+
+        assert_eq!(None, GreedyBestFirstSearch::search(&problem).unwrap());
+    }
+}
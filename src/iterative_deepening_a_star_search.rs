@@ -0,0 +1,350 @@
+use std::collections::HashMap;
+use std::vec::IntoIter;
+
+use crate::a_star_search::AStarSearch;
+use crate::problem::Problem;
+use crate::search_error::SearchError;
+use crate::search_problem::SearchProblem;
+use crate::search_result::SearchResult;
+
+#[derive(Debug, Clone)]
+pub struct IterativeDeepeningAStarSearch {}
+
+/// The outcome of one search up to an estimate threshold
+enum Outcome<S> {
+    Found(Vec<S>, f64),
+    /// The lowest estimate above the threshold, infinite if no branch was cut
+    Cutoff(f64),
+}
+
+/// A state on the current branch with the successors which are not taken yet
+struct Frame<S> {
+    state: S,
+    cost: f64,
+    successors: IntoIter<(S, f64)>,
+}
+
+/// The lowest cost each state was reached with during the current iteration,
+/// holding at most `capacity` states
+struct TranspositionTable<S> {
+    costs: HashMap<S, f64>,
+    capacity: usize,
+}
+
+impl IterativeDeepeningAStarSearch {
+    /// Returns an Option with the cheapest path and the search statistics,
+    /// or an error if the problem is invalid
+    ///
+    /// The search runs depth first searches which cut branches whose cost plus heuristic
+    /// exceeds a threshold, starting at the heuristic of the start and raised to the lowest cut
+    /// estimate after each run. Only the current branch is kept in memory.
+    /// The heuristic is the one of `AStarSearch`. The threshold is limited to the highest cost
+    /// of a path without loops through the nodes reachable from the start, and a goal which is
+    /// not reachable returns None without a search.
+    ///
+    /// # Arguments
+    ///
+    /// * `problem` The problem contains the start, the end and the nodes where to find the path
+    ///
+    /// # Example
+    /// ```
+    /// use rust_problem_search::iterative_deepening_a_star_search::IterativeDeepeningAStarSearch;
+    /// use rust_problem_search::graph::Graph;
+    ///
+    /// let ascii_map = Graph::from_ascii("S...\n.##.\n...G\n").unwrap();
+    /// let problem = ascii_map.get_path_problem().unwrap();
+    ///
+    /// let result = IterativeDeepeningAStarSearch::search(&problem).unwrap().unwrap();
+    ///
+    /// assert_eq!(5.0, result.cost);
+    /// ```
+    pub fn search(problem: &Problem) -> Result<Option<SearchResult>, SearchError> {
+        IterativeDeepeningAStarSearch::search_with_table(problem, None)
+    }
+
+    /// Returns an Option with the cheapest path and the search statistics,
+    /// or an error if the problem is invalid
+    ///
+    /// A transposition table remembers the lowest cost each node was reached with
+    /// during a run, so a node reached again on another branch without a lower cost
+    /// is not expanded twice. This saves time on maps with many equal paths,
+    /// like open areas, for the memory of the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `problem` The problem contains the start, the end and the nodes where to find the path
+    /// * `capacity` The largest number of nodes in the table
+    pub fn search_with_transposition_table(
+        problem: &Problem,
+        capacity: usize,
+    ) -> Result<Option<SearchResult>, SearchError> {
+        IterativeDeepeningAStarSearch::search_with_table(problem, Some(capacity))
+    }
+
+    /// Returns an Option with the cheapest path of states to a goal and the search statistics,
+    /// guided by the heuristic of the problem
+    ///
+    /// # Arguments
+    ///
+    /// * `problem` Any problem with an initial state, a goal test, successors and a heuristic
+    /// * `table_capacity` The largest number of states in the transposition table,
+    ///   None for no table
+    pub fn search_problem<P: SearchProblem>(
+        problem: &P,
+        table_capacity: Option<usize>,
+    ) -> Option<SearchResult<P::State>> {
+        IterativeDeepeningAStarSearch::search_with_heuristic(
+            problem,
+            table_capacity,
+            None,
+            |state| problem.heuristic(state),
+        )
+    }
+
+    fn search_with_table(
+        problem: &Problem,
+        table_capacity: Option<usize>,
+    ) -> Result<Option<SearchResult>, SearchError> {
+        problem.validate()?;
+        let (_, max_cost) = match problem.get_reachable_bound() {
+            Some(bound) => bound,
+            None => return Ok(None),
+        };
+
+        let heuristic = AStarSearch::get_heuristic(problem);
+        Ok(IterativeDeepeningAStarSearch::search_with_heuristic(
+            problem,
+            table_capacity,
+            Some(max_cost),
+            heuristic,
+        )
+        .map(|result| result.map(|id| problem.get_position(id))))
+    }
+
+    /// Raises the threshold until a goal is found, no branch is cut
+    /// or the threshold exceeds `max_threshold`
+    fn search_with_heuristic<P, H>(
+        problem: &P,
+        table_capacity: Option<usize>,
+        max_threshold: Option<f64>,
+        heuristic: H,
+    ) -> Option<SearchResult<P::State>>
+    where
+        P: SearchProblem,
+        H: Fn(&P::State) -> f64,
+    {
+        let mut table = table_capacity.map(|capacity| TranspositionTable {
+            costs: HashMap::new(),
+            capacity,
+        });
+        let mut threshold = heuristic(&problem.initial_state());
+        let mut expanded = 0;
+        let mut frontier_peak = 0;
+
+        loop {
+            if let Some(table) = table.as_mut() {
+                table.costs.clear();
+            }
+            match IterativeDeepeningAStarSearch::search_threshold(
+                problem,
+                &heuristic,
+                threshold,
+                &mut table,
+                &mut expanded,
+                &mut frontier_peak,
+            ) {
+                Outcome::Found(path, cost) => {
+                    return Some(SearchResult {
+                        length: path.len() - 1,
+                        path,
+                        cost,
+                        expanded,
+                        frontier_peak,
+                    })
+                }
+                Outcome::Cutoff(next_threshold) if next_threshold.is_infinite() => return None,
+                Outcome::Cutoff(next_threshold)
+                    if max_threshold.is_some_and(|maximum| next_threshold > maximum) =>
+                {
+                    return None
+                }
+                Outcome::Cutoff(next_threshold) => threshold = next_threshold,
+            }
+        }
+    }
+
+    /// Runs a depth first search which cuts the branches whose estimate exceeds the threshold
+    fn search_threshold<P, H>(
+        problem: &P,
+        heuristic: &H,
+        threshold: f64,
+        table: &mut Option<TranspositionTable<P::State>>,
+        expanded: &mut usize,
+        frontier_peak: &mut usize,
+    ) -> Outcome<P::State>
+    where
+        P: SearchProblem,
+        H: Fn(&P::State) -> f64,
+    {
+        let initial_state = problem.initial_state();
+        if problem.is_goal(&initial_state) {
+            return Outcome::Found(vec![initial_state], 0.0);
+        }
+
+        *expanded += 1;
+        let mut branch = vec![Frame {
+            successors: problem.successors(&initial_state).into_iter(),
+            state: initial_state,
+            cost: 0.0,
+        }];
+        let mut next_threshold = f64::INFINITY;
+
+        while let Some(frame) = branch.last_mut() {
+            let (state, step_cost) = match frame.successors.next() {
+                Some(successor) => successor,
+                None => {
+                    branch.pop();
+                    continue;
+                }
+            };
+            let cost = frame.cost + step_cost;
+            if branch.iter().any(|frame| frame.state == state) {
+                continue;
+            }
+            let estimate = cost + heuristic(&state);
+            if estimate > threshold {
+                next_threshold = next_threshold.min(estimate);
+                continue;
+            }
+            if problem.is_goal(&state) {
+                let mut path: Vec<P::State> = branch.into_iter().map(|frame| frame.state).collect();
+                path.push(state);
+                return Outcome::Found(path, cost);
+            }
+            if let Some(table) = table.as_mut() {
+                match table.costs.get(&state) {
+                    Some(reached) if *reached <= cost => continue,
+                    Some(_) => {
+                        table.costs.insert(state.clone(), cost);
+                    }
+                    None if table.costs.len() < table.capacity => {
+                        table.costs.insert(state.clone(), cost);
+                    }
+                    None => {}
+                }
+            }
+
+            *expanded += 1;
+            branch.push(Frame {
+                successors: problem.successors(&state).into_iter(),
+                state,
+                cost,
+            });
+            let held: usize = branch.iter().map(|frame| frame.successors.len() + 1).sum();
+            *frontier_peak = (*frontier_peak).max(held);
+        }
+        Outcome::Cutoff(next_threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Graph;
+    use crate::position::Position;
+
+    fn map() -> Problem {
+        let map = "
+ .......
+ .### #.
+S. #  G.
+ ...  ..
+";
+        Graph::from_ascii(map)
+            .unwrap()
+            .get_possible_problem()
+            .unwrap()
+    }
+
+    #[test]
+    fn search_returns_same_cost_as_a_star_search() {
+        let result = IterativeDeepeningAStarSearch::search(&map())
+            .unwrap()
+            .unwrap();
+        let a_star_result = AStarSearch::search(&map()).unwrap().unwrap();
+
+        assert_eq!(a_star_result.cost, result.cost);
+        assert_eq!(Position::new(2, 0), result.path[0]);
+        assert_eq!(Position::new(2, 6), result.path[result.length]);
+    }
+
+    #[test]
+    fn search_with_transposition_table_expands_fewer_nodes() {
+        let map = "
+S.....
+......
+......
+#####.
+G.....
+";
+        let problem = Graph::from_ascii(map).unwrap().get_path_problem().unwrap();
+        let result = IterativeDeepeningAStarSearch::search(&problem)
+            .unwrap()
+            .unwrap();
+        let table_result =
+            IterativeDeepeningAStarSearch::search_with_transposition_table(&problem, 30)
+                .unwrap()
+                .unwrap();
+
+        assert_eq!(14.0, result.cost);
+        assert_eq!(14.0, table_result.cost);
+        assert!(table_result.expanded < result.expanded);
+    }
+
+    #[test]
+    fn search_with_full_transposition_table_returns_same_cost() {
+        let result = IterativeDeepeningAStarSearch::search_with_transposition_table(&map(), 2)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            AStarSearch::search(&map()).unwrap().unwrap().cost,
+            result.cost
+        );
+    }
+
+    #[test]
+    fn search_without_path_returns_none() {
+        let problem = Graph::from_ascii("S.\n##\n.G")
+            .unwrap()
+            .get_path_problem()
+            .unwrap();
+
+        assert_eq!(
+            None,
+            IterativeDeepeningAStarSearch::search(&problem).unwrap()
+        );
+    }
+
+    #[test]
+    fn search_without_path_on_open_map_returns_none() {
+        let map = "
+S......
+.......
+.......
+.......
+.....##
+.....#G
+";
+        let problem = Graph::from_ascii(map).unwrap().get_path_problem().unwrap();
+
+        assert_eq!(
+            None,
+            IterativeDeepeningAStarSearch::search(&problem).unwrap()
+        );
+        assert_eq!(
+            None,
+            IterativeDeepeningAStarSearch::search_with_transposition_table(&problem, 8).unwrap()
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use std::vec::IntoIter;
+
+use crate::problem::Problem;
+use crate::search_error::SearchError;
+use crate::search_problem::SearchProblem;
+use crate::search_result::SearchResult;
+
+#[derive(Debug, Clone)]
+pub struct IterativeDeepeningSearch {}
+
+/// The outcome of one depth limited search
+enum Outcome<S> {
+    Found(Vec<S>, f64),
+    /// No goal was found, but branches were cut at the depth limit
+    Cutoff,
+    /// No goal was found and no branch was cut, a deeper search finds nothing either
+    Exhausted,
+}
+
+/// A state on the current branch with the successors which are not taken yet
+struct Frame<S> {
+    state: S,
+    cost: f64,
+    successors: IntoIter<(S, f64)>,
+}
+
+impl IterativeDeepeningSearch {
+    /// Returns an Option with the shortest path in steps and the search statistics,
+    /// or an error if the problem is invalid
+    ///
+    /// The search runs depth first searches with a growing depth limit and only keeps
+    /// the current branch in memory, instead of all explored nodes. Nodes near the start
+    /// are expanded again in each run, so it takes more time than the breath first search.
+    /// The depth is limited to the number of nodes reachable from the start minus one,
+    /// the most steps of a path without loops, and a goal which is not reachable
+    /// returns None without a search.
+    ///
+    /// # Arguments
+    ///
+    /// * `problem` The problem contains the start, the end and the nodes where to find the path
+    ///
+    /// # Example
+    /// ```
+    /// use rust_problem_search::iterative_deepening_search::IterativeDeepeningSearch;
+    /// use rust_problem_search::graph::Graph;
+    ///
+    /// let ascii_map = Graph::from_ascii("S.#\n..#\n#.G\n").unwrap();
+    /// let problem = ascii_map.get_path_problem().unwrap();
+    ///
+    /// let result = IterativeDeepeningSearch::search(&problem).unwrap().unwrap();
+    ///
+    /// assert_eq!(4, result.length);
+    /// ```
+    pub fn search(problem: &Problem) -> Result<Option<SearchResult>, SearchError> {
+        problem.validate()?;
+        let (reachable, _) = match problem.get_reachable_bound() {
+            Some(bound) => bound,
+            None => return Ok(None),
+        };
+        Ok(
+            IterativeDeepeningSearch::search_problem(problem, Some(reachable - 1))
+                .map(|result| result.map(|id| problem.get_position(id))),
+        )
+    }
+
+    /// Returns an Option with the shortest path of at most `depth_limit` steps
+    /// and the search statistics, or an error if the problem is invalid
+    ///
+    /// # Arguments
+    ///
+    /// * `problem` The problem contains the start, the end and the nodes where to find the path
+    /// * `depth_limit` The largest number of steps from the start
+    pub fn search_with_limit(
+        problem: &Problem,
+        depth_limit: usize,
+    ) -> Result<Option<SearchResult>, SearchError> {
+        problem.validate()?;
+        Ok(
+            IterativeDeepeningSearch::search_problem(problem, Some(depth_limit))
+                .map(|result| result.map(|id| problem.get_position(id))),
+        )
+    }
+
+    /// Returns an Option with the path of states with the fewest steps to a goal
+    /// and the search statistics
+    ///
+    /// A state is never repeated on the current branch, other than that
+    /// states are not remembered between branches.
+    ///
+    /// # Arguments
+    ///
+    /// * `problem` Any problem with an initial state, a goal test and successors
+    /// * `depth_limit` The largest number of steps from the initial state, None for no limit
+    pub fn search_problem<P: SearchProblem>(
+        problem: &P,
+        depth_limit: Option<usize>,
+    ) -> Option<SearchResult<P::State>> {
+        let mut expanded = 0;
+        let mut frontier_peak = 0;
+        let mut limit = 0;
+
+        loop {
+            match IterativeDeepeningSearch::search_depth(
+                problem,
+                limit,
+                &mut expanded,
+                &mut frontier_peak,
+            ) {
+                Outcome::Found(path, cost) => {
+                    return Some(SearchResult {
+                        length: path.len() - 1,
+                        path,
+                        cost,
+                        expanded,
+                        frontier_peak,
+                    })
+                }
+                Outcome::Exhausted => return None,
+                Outcome::Cutoff if depth_limit.is_some_and(|maximum| limit >= maximum) => {
+                    return None
+                }
+                Outcome::Cutoff => limit += 1,
+            }
+        }
+    }
+
+    /// Runs a depth first search which does not expand states `limit` steps away from the start
+    fn search_depth<P: SearchProblem>(
+        problem: &P,
+        limit: usize,
+        expanded: &mut usize,
+        frontier_peak: &mut usize,
+    ) -> Outcome<P::State> {
+        let initial_state = problem.initial_state();
+        if problem.is_goal(&initial_state) {
+            return Outcome::Found(vec![initial_state], 0.0);
+        }
+        if limit == 0 {
+            return Outcome::Cutoff;
+        }
+
+        *expanded += 1;
+        let mut branch = vec![Frame {
+            successors: problem.successors(&initial_state).into_iter(),
+            state: initial_state,
+            cost: 0.0,
+        }];
+        let mut cutoff = false;
+
+        while let Some(frame) = branch.last_mut() {
+            let (state, step_cost) = match frame.successors.next() {
+                Some(successor) => successor,
+                None => {
+                    branch.pop();
+                    continue;
+                }
+            };
+            let cost = frame.cost + step_cost;
+            if branch.iter().any(|frame| frame.state == state) {
+                continue;
+            }
+            if problem.is_goal(&state) {
+                let mut path: Vec<P::State> = branch.into_iter().map(|frame| frame.state).collect();
+                path.push(state);
+                return Outcome::Found(path, cost);
+            }
+            if branch.len() >= limit {
+                cutoff = true;
+                continue;
+            }
+
+            *expanded += 1;
+            branch.push(Frame {
+                successors: problem.successors(&state).into_iter(),
+                state,
+                cost,
+            });
+            let held: usize = branch.iter().map(|frame| frame.successors.len() + 1).sum();
+            *frontier_peak = (*frontier_peak).max(held);
+        }
+
+        if cutoff {
+            Outcome::Cutoff
+        } else {
+            Outcome::Exhausted
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::breath_first_search::BreathFirstSearch;
+    use crate::graph::Graph;
+    use crate::position::Position;
+
+    fn map() -> Problem {
+        let map = "
+.....
+.###.
+S....
+####.
+G....
+";
+        Graph::from_ascii(map).unwrap().get_path_problem().unwrap()
+    }
+
+    #[test]
+    fn search_returns_same_length_as_breath_first_search() {
+        let result = IterativeDeepeningSearch::search(&map()).unwrap().unwrap();
+        let breath_first_result = BreathFirstSearch::search(&map()).unwrap().unwrap();
+
+        assert_eq!(10, result.length);
+        assert_eq!(breath_first_result.length, result.length);
+        assert_eq!(10.0, result.cost);
+        assert_eq!(Position::new(2, 0), result.path[0]);
+        assert_eq!(Position::new(4, 0), result.path[10]);
+    }
+
+    #[test]
+    fn search_with_limit_below_shortest_path_returns_none() {
+        assert_eq!(
+            None,
+            IterativeDeepeningSearch::search_with_limit(&map(), 9).unwrap()
+        );
+        assert!(IterativeDeepeningSearch::search_with_limit(&map(), 10)
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn search_without_path_returns_none() {
+        let problem = Graph::from_ascii("S.\n##\n.G")
+            .unwrap()
+            .get_path_problem()
+            .unwrap();
+
+        assert_eq!(None, IterativeDeepeningSearch::search(&problem).unwrap());
+    }
+
+    #[test]
+    fn search_without_path_on_open_map_returns_none() {
+        let map = "
+S......
+.......
+.......
+.......
+.....##
+.....#G
+";
+        let problem = Graph::from_ascii(map).unwrap().get_path_problem().unwrap();
+
+        assert_eq!(None, IterativeDeepeningSearch::search(&problem).unwrap());
+    }
+
+    #[test]
+    fn search_with_start_equals_goal_returns_result() {
+        let graph = Graph::new(vec![1, 1], 2, 1).unwrap();
+        let problem = Problem::new(
+            graph.get_path_nodes(),
+            graph.get_node_id_at_position(Position::new(0, 1)),
+            graph.get_node_id_at_position(Position::new(0, 1)),
+            graph.size,
+        )
+        .unwrap();
+        let result = IterativeDeepeningSearch::search(&problem).unwrap().unwrap();
+
+        assert_eq!(vec![Position::new(0, 1)], result.path);
+        assert_eq!(0, result.expanded);
+    }
+}
@@ -0,0 +1,450 @@
+use crate::a_star_search::AStarSearch;
+use crate::connectivity::Connectivity;
+use crate::graph::Graph;
+use crate::node::NodeId;
+use crate::position::Position;
+use crate::problem::Problem;
+use crate::search_error::SearchError;
+use crate::search_problem::SearchProblem;
+use crate::search_result::SearchResult;
+
+#[derive(Debug, Clone)]
+pub struct JumpPointSearch {}
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+/// A reached jump point with the row and column direction of the jump which reached it,
+/// (0, 0) for the start
+struct JumpPoint {
+    id: NodeId,
+    direction: (i32, i32),
+}
+
+/// The jump points of a problem on the grid of a graph, where each step costs the same
+struct JumpPoints<'a> {
+    graph: &'a Graph,
+    problem: &'a Problem,
+    goal: Position,
+}
+
+impl JumpPointSearch {
+    /// Returns an Option with the shortest path and the search statistics,
+    /// or an error if the problem is invalid or does not belong to the graph
+    ///
+    /// The search only expands jump points, the nodes where an optimal path may turn,
+    /// and skips the symmetric paths between them by walking straight or diagonal lines
+    /// on the tiles of the graph. Every walkable node of the problem counts as the same cost,
+    /// a straight step as one and a diagonal step as the square root of two, so the path
+    /// has the same length as the one of the breath first search on four-connected graphs.
+    /// The cost of the result is the traversal cost of the tiles on the path, which is the
+    /// cheapest cost as long as all walkable tiles are of the same kind.
+    ///
+    /// The connectivity of the graph defines the moves, the problem defines which nodes
+    /// are walkable, e.g. the path nodes or the possible nodes of the graph.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` The graph the nodes of the problem are build from
+    /// * `problem` The problem contains the start, the end and the nodes where to find the path
+    ///
+    /// # Example
+    /// ```
+    /// use rust_problem_search::jump_point_search::JumpPointSearch;
+    /// use rust_problem_search::graph::Graph;
+    ///
+    /// let ascii_map = Graph::from_ascii("S   \n ## \n   G\n").unwrap();
+    /// let problem = ascii_map.get_possible_problem().unwrap();
+    ///
+    /// let result = JumpPointSearch::search(&ascii_map.graph, &problem).unwrap().unwrap();
+    ///
+    /// assert_eq!(5, result.length);
+    /// ```
+    pub fn search(graph: &Graph, problem: &Problem) -> Result<Option<SearchResult>, SearchError> {
+        problem.validate()?;
+        if problem.get_nodes().len() != graph.size {
+            return Err(SearchError::InvalidTileCount {
+                expected: graph.size,
+                actual: problem.get_nodes().len(),
+            });
+        }
+
+        let jump_points = JumpPoints {
+            graph,
+            problem,
+            goal: problem.get_position(problem.goal),
+        };
+        let result = AStarSearch::search_with_estimate(&jump_points, |jump_point, cost| {
+            cost + jump_points.heuristic(jump_point)
+        });
+        Ok(result.map(|result| jump_points.get_result(result)))
+    }
+}
+
+impl JumpPoints<'_> {
+    /// Returns true if the position is inside of the graph and its node is walkable
+    fn is_walkable(&self, row: i32, column: i32) -> bool {
+        row >= 0
+            && column >= 0
+            && row < self.graph.height as i32
+            && column < self.graph.width as i32
+            && self.problem.get_nodes()[(row as u32 * self.graph.width + column as u32) as usize]
+                .walkable
+    }
+
+    /// Returns the next jump point from a position in a direction,
+    /// or None if the direction runs into a blocked tile first
+    fn jump(&self, from: Position, direction: (i32, i32)) -> Option<Position> {
+        let (row_step, column_step) = direction;
+        let diagonal = row_step != 0 && column_step != 0;
+        let (mut row, mut column) = (from.row as i32, from.column as i32);
+        loop {
+            if diagonal
+                && self.graph.connectivity == Connectivity::EightWithoutCornerCutting
+                && !(self.is_walkable(row + row_step, column)
+                    && self.is_walkable(row, column + column_step))
+            {
+                return None;
+            }
+            row += row_step;
+            column += column_step;
+            if !self.is_walkable(row, column) {
+                return None;
+            }
+            let position = Position::new(row as u32, column as u32);
+            if position == self.goal || self.has_forced_neighbour(row, column, direction) {
+                return Some(position);
+            }
+            // a diagonal line, or a vertical line on a four-connected graph,
+            // stops where one of its side lines finds a jump point
+            let sides: &[(i32, i32)] = if diagonal {
+                &[(row_step, 0), (0, column_step)]
+            } else if row_step != 0 && self.graph.connectivity == Connectivity::Four {
+                &[(0, 1), (0, -1)]
+            } else {
+                &[]
+            };
+            if sides
+                .iter()
+                .any(|side| self.jump(position, *side).is_some())
+            {
+                return Some(position);
+            }
+        }
+    }
+
+    /// Returns true if an optimal path reaching the position in the direction may have to turn,
+    /// because a tile next to the previous position is blocked
+    fn has_forced_neighbour(&self, row: i32, column: i32, direction: (i32, i32)) -> bool {
+        let (row_step, column_step) = direction;
+        let open = |row_offset: i32, column_offset: i32| {
+            self.is_walkable(row + row_offset, column + column_offset)
+        };
+        match (self.graph.connectivity, row_step, column_step) {
+            (Connectivity::Four, 0, _) | (Connectivity::EightWithoutCornerCutting, 0, _) => {
+                (open(1, 0) && !open(1, -column_step)) || (open(-1, 0) && !open(-1, -column_step))
+            }
+            (Connectivity::EightWithoutCornerCutting, _, 0) => {
+                (open(0, 1) && !open(-row_step, 1)) || (open(0, -1) && !open(-row_step, -1))
+            }
+            (Connectivity::Eight, 0, _) => {
+                (open(1, column_step) && !open(1, 0)) || (open(-1, column_step) && !open(-1, 0))
+            }
+            (Connectivity::Eight, _, 0) => {
+                (open(row_step, 1) && !open(0, 1)) || (open(row_step, -1) && !open(0, -1))
+            }
+            (Connectivity::Eight, _, _) => {
+                (open(row_step, -column_step) && !open(0, -column_step))
+                    || (open(-row_step, column_step) && !open(-row_step, 0))
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the directions to jump in from a jump point,
+    /// which are all directions at the start and the natural and forced ones after
+    fn get_directions(&self, position: Position, direction: (i32, i32)) -> Vec<(i32, i32)> {
+        let (row_step, column_step) = direction;
+        let (row, column) = (position.row as i32, position.column as i32);
+        let blocked = |row_offset: i32, column_offset: i32| {
+            !self.is_walkable(row + row_offset, column + column_offset)
+        };
+        if direction == (0, 0) {
+            return self.graph.connectivity.offsets().to_vec();
+        }
+
+        let mut directions = vec![direction];
+        match (self.graph.connectivity, row_step, column_step) {
+            (Connectivity::Four, 0, _) => {
+                for side in [1, -1] {
+                    if !blocked(side, 0) && blocked(side, -column_step) {
+                        directions.push((side, 0));
+                    }
+                }
+            }
+            (Connectivity::Four, _, _) => directions.extend([(0, 1), (0, -1)]),
+            (Connectivity::Eight, 0, _) => {
+                for side in [1, -1] {
+                    if blocked(side, 0) {
+                        directions.push((side, column_step));
+                    }
+                }
+            }
+            (Connectivity::Eight, _, 0) => {
+                for side in [1, -1] {
+                    if blocked(0, side) {
+                        directions.push((row_step, side));
+                    }
+                }
+            }
+            (Connectivity::Eight, _, _) => {
+                directions.extend([(row_step, 0), (0, column_step)]);
+                if blocked(0, -column_step) {
+                    directions.push((row_step, -column_step));
+                }
+                if blocked(-row_step, 0) {
+                    directions.push((-row_step, column_step));
+                }
+            }
+            (Connectivity::EightWithoutCornerCutting, 0, _) => {
+                directions.extend([(1, column_step), (-1, column_step), (1, 0), (-1, 0)])
+            }
+            (Connectivity::EightWithoutCornerCutting, _, 0) => {
+                directions.extend([(row_step, 1), (row_step, -1), (0, 1), (0, -1)])
+            }
+            (Connectivity::EightWithoutCornerCutting, _, _) => {
+                directions.extend([(row_step, 0), (0, column_step)])
+            }
+        }
+        directions
+    }
+
+    /// Returns the result with each step between the jump points on the path
+    /// and the traversal cost of the tiles
+    fn get_result(&self, result: SearchResult<JumpPoint>) -> SearchResult {
+        let mut path = vec![self.problem.get_position(result.path[0].id)];
+        for jump_point in &result.path[1..] {
+            let (row_step, column_step) = jump_point.direction;
+            let target = self.problem.get_position(jump_point.id);
+            while path[path.len() - 1] != target {
+                let previous = path[path.len() - 1];
+                path.push(Position::new(
+                    (previous.row as i32 + row_step) as u32,
+                    (previous.column as i32 + column_step) as u32,
+                ));
+            }
+        }
+        let cost = path
+            .windows(2)
+            .filter_map(|step| {
+                let previous = &self.problem.get_nodes()[self.graph.get_index_at_position(step[0])];
+                self.problem.get_nodes()[self.graph.get_index_at_position(step[1])]
+                    .cost_from(previous)
+            })
+            .sum();
+        SearchResult {
+            length: path.len() - 1,
+            path,
+            cost,
+            expanded: result.expanded,
+            frontier_peak: result.frontier_peak,
+        }
+    }
+}
+
+impl SearchProblem for JumpPoints<'_> {
+    type State = JumpPoint;
+
+    fn initial_state(&self) -> JumpPoint {
+        JumpPoint {
+            id: self.problem.start,
+            direction: (0, 0),
+        }
+    }
+
+    fn is_goal(&self, state: &JumpPoint) -> bool {
+        state.id == self.problem.goal
+    }
+
+    /// Returns the next jump points with the distance to them
+    fn successors(&self, state: &JumpPoint) -> Vec<(JumpPoint, f64)> {
+        let position = self.problem.get_position(state.id);
+        self.get_directions(position, state.direction)
+            .into_iter()
+            .filter_map(|direction| {
+                let jump_point = self.jump(position, direction)?;
+                let steps = f64::from(jump_point.distance(&position));
+                let distance = if direction.0 != 0 && direction.1 != 0 {
+                    steps / 2.0 * std::f64::consts::SQRT_2
+                } else {
+                    steps
+                };
+                Some((
+                    JumpPoint {
+                        id: self.graph.get_node_id_at_position(jump_point),
+                        direction,
+                    },
+                    distance,
+                ))
+            })
+            .collect()
+    }
+
+    /// Returns the manhattan distance to the goal on four-connected graphs,
+    /// the octile distance otherwise
+    fn heuristic(&self, state: &JumpPoint) -> f64 {
+        let position = self.problem.get_position(state.id);
+        if self.graph.connectivity == Connectivity::Four {
+            f64::from(position.distance(&self.goal))
+        } else {
+            position.octile_distance(&self.goal)
+        }
+    }
+
+    fn state_index(&self, state: &JumpPoint) -> Option<usize> {
+        let (row_step, column_step) = state.direction;
+        Some(state.id.index() * 9 + ((row_step + 1) * 3 + column_step + 1) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::breath_first_search::BreathFirstSearch;
+    use crate::uniform_cost_search::UniformCostSearch;
+
+    /// Returns maps with random ground tiles, the same ones on every run
+    fn random_graphs(count: usize, connectivity: Connectivity) -> Vec<Graph> {
+        let mut seed: u64 = 42;
+        (0..count)
+            .map(|_| {
+                let tiles = (0..144)
+                    .map(|_| {
+                        seed = seed
+                            .wrapping_mul(6364136223846793005)
+                            .wrapping_add(1442695040888963407);
+                        if (seed >> 33) % 10 < 3 {
+                            2
+                        } else {
+                            1
+                        }
+                    })
+                    .collect();
+                Graph::new(tiles, 12, 12)
+                    .unwrap()
+                    .with_connectivity(connectivity)
+            })
+            .collect()
+    }
+
+    fn corner_problem(graph: &Graph, nodes: Vec<crate::node::Node>) -> Option<Problem> {
+        Problem::new(
+            nodes,
+            graph.get_node_id_at_position(Position::new(0, 0)),
+            graph.get_node_id_at_position(Position::new(graph.height - 1, graph.width - 1)),
+            graph.size,
+        )
+        .ok()
+    }
+
+    fn assert_connected(problem: &Problem, graph: &Graph, result: &SearchResult) {
+        assert!(result.path.windows(2).all(|step| {
+            problem.get_nodes()[graph.get_index_at_position(step[0])]
+                .neighbours
+                .contains(&graph.get_node_id_at_position(step[1]))
+        }));
+    }
+
+    #[test]
+    fn search_returns_same_length_as_breath_first_search() {
+        let mut compared = 0;
+        for graph in random_graphs(100, Connectivity::Four) {
+            let problem = match corner_problem(&graph, graph.get_path_nodes()) {
+                Some(problem) => problem,
+                None => continue,
+            };
+            let result = JumpPointSearch::search(&graph, &problem).unwrap();
+            let breath_first_result = BreathFirstSearch::search(&problem).unwrap();
+
+            assert_eq!(
+                breath_first_result.as_ref().map(|result| result.length),
+                result.as_ref().map(|result| result.length)
+            );
+            if let Some(result) = result {
+                assert_connected(&problem, &graph, &result);
+                compared += 1;
+            }
+        }
+        assert!(compared > 10);
+    }
+
+    #[test]
+    fn search_with_eight_returns_same_cost_as_uniform_cost_search() {
+        for connectivity in [Connectivity::Eight, Connectivity::EightWithoutCornerCutting] {
+            for graph in random_graphs(100, connectivity) {
+                let problem = match corner_problem(&graph, graph.get_path_nodes()) {
+                    Some(problem) => problem,
+                    None => continue,
+                };
+                let result = JumpPointSearch::search(&graph, &problem).unwrap();
+                let uniform_cost_result = UniformCostSearch::search(&problem).unwrap();
+
+                match (uniform_cost_result, result.as_ref()) {
+                    (Some(expected), Some(result)) => {
+                        assert!((expected.cost - result.cost).abs() < 1e-9)
+                    }
+                    (expected, result) => assert_eq!(expected.is_some(), result.is_some()),
+                }
+                if let Some(result) = result {
+                    assert_connected(&problem, &graph, &result);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn search_on_open_area_expands_fewer_nodes_than_breath_first_search() {
+        for connectivity in [Connectivity::Four, Connectivity::Eight] {
+            let graph = Graph::new(vec![0; 1600], 40, 40)
+                .unwrap()
+                .with_connectivity(connectivity);
+            let problem = Problem::new(
+                graph.get_possible_nodes(),
+                graph.get_node_id_at_position(Position::new(3, 5)),
+                graph.get_node_id_at_position(Position::new(36, 30)),
+                graph.size,
+            )
+            .unwrap();
+            let result = JumpPointSearch::search(&graph, &problem).unwrap().unwrap();
+            let breath_first_result = BreathFirstSearch::search(&problem).unwrap().unwrap();
+
+            assert_eq!(breath_first_result.length, result.length);
+            assert_eq!(breath_first_result.cost, result.cost);
+            assert!(result.expanded * 10 < breath_first_result.expanded);
+        }
+    }
+
+    #[test]
+    fn search_without_path_returns_none() {
+        let ascii_map = Graph::from_ascii("S.\n##\n.G").unwrap();
+        let problem = ascii_map.get_path_problem().unwrap();
+
+        assert_eq!(
+            None,
+            JumpPointSearch::search(&ascii_map.graph, &problem).unwrap()
+        );
+    }
+
+    #[test]
+    fn search_with_problem_of_other_graph_returns_error() {
+        let ascii_map = Graph::from_ascii("S.\n.G").unwrap();
+        let problem = ascii_map.get_path_problem().unwrap();
+        let graph = Graph::new(vec![1; 6], 3, 2).unwrap();
+
+        assert_eq!(
+            Err(SearchError::InvalidTileCount {
+                expected: 6,
+                actual: 4
+            }),
+            JumpPointSearch::search(&graph, &problem)
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use std::collections::HashSet;
+
+use crate::a_star_search::AStarSearch;
+use crate::node::NodeId;
+use crate::problem::Problem;
+use crate::search_error::SearchError;
+use crate::search_problem::SearchProblem;
+use crate::search_result::SearchResult;
+
+#[derive(Debug, Clone)]
+pub struct KShortestPathsSearch {}
+
+impl KShortestPathsSearch {
+    /// Returns up to `count` cheapest paths without loops ordered by their cost,
+    /// or an error if the problem is invalid
+    ///
+    /// The first path is found with the A* search. Each further path (Yen's algorithm)
+    /// leaves a path found before at one of its nodes, the spur node, and continues with the
+    /// cheapest path to the goal which neither visits the nodes before the spur node
+    /// nor takes a step from the spur node already taken by a path with the same beginning.
+    /// The statistics of each result are the ones of the search which found its path.
+    ///
+    /// # Arguments
+    ///
+    /// * `problem` The problem contains the start, the end and the nodes where to find the paths
+    /// * `count` The largest number of paths to return
+    ///
+    /// # Example
+    /// ```
+    /// use rust_problem_search::graph::Graph;
+    /// use rust_problem_search::k_shortest_paths_search::KShortestPathsSearch;
+    ///
+    /// let ascii_map = Graph::from_ascii("S..\n.#.\n..G").unwrap();
+    /// let problem = ascii_map.get_path_problem().unwrap();
+    ///
+    /// let results = KShortestPathsSearch::search(&problem, 3).unwrap();
+    ///
+    /// assert_eq!(2, results.len());
+    /// assert_eq!(4.0, results[1].cost);
+    /// ```
+    pub fn search(problem: &Problem, count: usize) -> Result<Vec<SearchResult>, SearchError> {
+        problem.validate()?;
+        if count == 0 {
+            return Ok(vec![]);
+        }
+        let first = match AStarSearch::search_problem(problem) {
+            Some(result) => result,
+            None => return Ok(vec![]),
+        };
+
+        let mut paths = vec![first];
+        let mut candidates: Vec<SearchResult<NodeId>> = vec![];
+        while paths.len() < count {
+            let last = &paths[paths.len() - 1].path;
+            let costs = KShortestPathsSearch::get_costs(problem, last);
+            for spur_index in 0..last.len() - 1 {
+                let root = &last[..=spur_index];
+                let mut spur_problem = SpurProblem {
+                    problem,
+                    spur: last[spur_index],
+                    blocked_nodes: vec![false; problem.get_nodes().len()],
+                    blocked_steps: HashSet::new(),
+                };
+                for id in &root[..spur_index] {
+                    spur_problem.blocked_nodes[id.index()] = true;
+                }
+                for path in &paths {
+                    if path.path.len() > spur_index + 1 && path.path[..=spur_index] == *root {
+                        spur_problem
+                            .blocked_steps
+                            .insert((path.path[spur_index], path.path[spur_index + 1]));
+                    }
+                }
+
+                if let Some(spur_result) = AStarSearch::search_problem(&spur_problem) {
+                    let mut path = root[..spur_index].to_vec();
+                    path.extend(spur_result.path);
+                    if candidates.iter().any(|candidate| candidate.path == path) {
+                        continue;
+                    }
+                    candidates.push(SearchResult {
+                        length: path.len() - 1,
+                        path,
+                        cost: costs[spur_index] + spur_result.cost,
+                        expanded: spur_result.expanded,
+                        frontier_peak: spur_result.frontier_peak,
+                    });
+                }
+            }
+
+            // takes the cheapest candidate, the one with fewer steps for equal costs
+            let next = candidates
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| a.cost.total_cmp(&b.cost).then(a.length.cmp(&b.length)))
+                .map(|(index, _)| index);
+            match next {
+                Some(index) => paths.push(candidates.swap_remove(index)),
+                None => break,
+            }
+        }
+
+        Ok(paths
+            .into_iter()
+            .map(|result| result.map(|id| problem.get_position(id)))
+            .collect())
+    }
+
+    /// Returns the cost from the start to each node of the path
+    fn get_costs(problem: &Problem, path: &[NodeId]) -> Vec<f64> {
+        let mut costs = vec![0.0];
+        for step in path.windows(2) {
+            let node = &problem.get_nodes()[step[0].index()];
+            let cost = problem.get_nodes()[step[1].index()]
+                .cost_from(node)
+                .unwrap_or(f64::INFINITY);
+            costs.push(costs[costs.len() - 1] + cost);
+        }
+        costs
+    }
+}
+
+/// The problem from a spur node to the goal without the blocked nodes and steps
+struct SpurProblem<'a> {
+    problem: &'a Problem,
+    spur: NodeId,
+    blocked_nodes: Vec<bool>,
+    blocked_steps: HashSet<(NodeId, NodeId)>,
+}
+
+impl SearchProblem for SpurProblem<'_> {
+    type State = NodeId;
+
+    fn initial_state(&self) -> NodeId {
+        self.spur
+    }
+
+    fn is_goal(&self, state: &NodeId) -> bool {
+        self.problem.is_goal(state)
+    }
+
+    fn successors(&self, state: &NodeId) -> Vec<(NodeId, f64)> {
+        self.problem
+            .successors(state)
+            .into_iter()
+            .filter(|(next, _)| {
+                !self.blocked_nodes[next.index()] && !self.blocked_steps.contains(&(*state, *next))
+            })
+            .collect()
+    }
+
+    fn heuristic(&self, state: &NodeId) -> f64 {
+        self.problem.heuristic(state)
+    }
+
+    fn state_index(&self, state: &NodeId) -> Option<usize> {
+        self.problem.state_index(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Graph;
+    use crate::position::Position;
+
+    fn map() -> &'static str {
+        "
+S....
+.#.#.
+.....
+.#.#.
+....G
+"
+    }
+
+    /// Returns the costs of all paths without loops from the start to the goal
+    fn get_all_costs(problem: &Problem, path: &mut Vec<NodeId>, cost: f64, costs: &mut Vec<f64>) {
+        let last = path[path.len() - 1];
+        if problem.is_goal(&last) {
+            costs.push(cost);
+            return;
+        }
+        for (next, step_cost) in problem.successors(&last) {
+            if !path.contains(&next) {
+                path.push(next);
+                get_all_costs(problem, path, cost + step_cost, costs);
+                path.pop();
+            }
+        }
+    }
+
+    #[test]
+    fn search_returns_cheapest_paths_in_order() {
+        let problem = Graph::from_ascii(map())
+            .unwrap()
+            .get_path_problem()
+            .unwrap();
+        let results = KShortestPathsSearch::search(&problem, 8).unwrap();
+
+        let mut costs = vec![];
+        get_all_costs(&problem, &mut vec![problem.start], 0.0, &mut costs);
+        costs.sort_by(|a, b| a.total_cmp(b));
+        assert_eq!(
+            costs[..8].to_vec(),
+            results
+                .iter()
+                .map(|result| result.cost)
+                .collect::<Vec<f64>>()
+        );
+    }
+
+    #[test]
+    fn search_returns_different_paths_without_loops() {
+        let problem = Graph::from_ascii(map())
+            .unwrap()
+            .get_path_problem()
+            .unwrap();
+        let results = KShortestPathsSearch::search(&problem, 10).unwrap();
+
+        for (i, result) in results.iter().enumerate() {
+            assert_eq!(Position::new(0, 0), result.path[0]);
+            assert_eq!(Position::new(4, 4), result.path[result.length]);
+            let positions: HashSet<&Position> = result.path.iter().collect();
+            assert_eq!(result.path.len(), positions.len());
+            assert!(results[..i].iter().all(|other| other.path != result.path));
+        }
+    }
+
+    #[test]
+    fn search_with_fewer_paths_returns_all_paths() {
+        let problem = Graph::from_ascii("S.\n.G")
+            .unwrap()
+            .get_path_problem()
+            .unwrap();
+        let results = KShortestPathsSearch::search(&problem, 5).unwrap();
+
+        assert_eq!(2, results.len());
+        assert_eq!(2.0, results[0].cost);
+        assert_eq!(2.0, results[1].cost);
+    }
+
+    #[test]
+    fn search_without_path_returns_empty_list() {
+        let problem = Graph::from_ascii("S#G")
+            .unwrap()
+            .get_path_problem()
+            .unwrap();
+
+        assert_eq!(
+            Vec::<SearchResult>::new(),
+            KShortestPathsSearch::search(&problem, 3).unwrap()
+        );
+    }
+}
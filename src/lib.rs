@@ -1,6 +1,31 @@
+pub mod a_star_search;
+pub mod ascii_map;
 pub mod breath_first_search;
+pub mod components;
+pub mod connectivity;
+pub mod d_star_lite;
+pub mod depth_first_search;
+pub mod distance_field;
+pub mod flow_field;
 pub mod graph;
+pub mod greedy_best_first_search;
+pub mod iterative_deepening_a_star_search;
+pub mod iterative_deepening_search;
+pub mod jump_point_search;
+pub mod k_shortest_paths_search;
+pub mod moving_ai;
+pub mod multi_problem;
+pub mod mutable_graph;
+pub mod nearest_goal_search;
 pub mod node;
 pub mod position;
 pub mod problem;
+pub mod route_search;
+pub mod search_error;
+pub mod search_problem;
+pub mod search_result;
+pub mod theta_star_search;
 pub mod tile;
+pub mod tour_search;
+pub mod uniform_cost_search;
+pub mod weighted_a_star_search;
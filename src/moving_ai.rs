@@ -0,0 +1,438 @@
+use crate::connectivity::Connectivity;
+use crate::graph::Graph;
+use crate::position::Position;
+use crate::problem::Problem;
+use crate::search_error::SearchError;
+use crate::search_result::SearchResult;
+use crate::tile::Tile;
+
+/// The allowed difference between a found and an optimal length,
+/// the scenario files round the optimal lengths
+const TOLERANCE: f64 = 1e-4;
+
+#[derive(Debug, Clone, PartialEq)]
+/// A single line of a Moving AI scenario file
+pub struct Scenario {
+    pub bucket: u32,
+    pub map: String,
+    pub width: u32,
+    pub height: u32,
+    pub start: Position,
+    pub goal: Position,
+    pub optimal_length: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The result of a search for a scenario
+pub struct ScenarioOutcome {
+    pub scenario: Scenario,
+    /// The cost of the found path, None if no path was found
+    pub length: Option<f64>,
+    /// The number of expanded nodes
+    pub expanded: usize,
+}
+
+impl ScenarioOutcome {
+    /// Returns true if the found path has the optimal length of the scenario
+    pub fn is_optimal(&self) -> bool {
+        self.length
+            .is_some_and(|length| (length - self.scenario.optimal_length).abs() < TOLERANCE)
+    }
+}
+
+impl Scenario {
+    /// Returns the scenarios of a Moving AI `.map.scen` file
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The content of the scenario file
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_problem_search::moving_ai::Scenario;
+    /// use rust_problem_search::position::Position;
+    ///
+    /// let input = "version 1\n0\tarena.map\t4\t3\t0\t1\t3\t2\t3.41421356\n";
+    /// let scenarios = Scenario::parse(input).unwrap();
+    ///
+    /// assert_eq!(Position::new(1, 0), scenarios[0].start);
+    /// assert_eq!(Position::new(2, 3), scenarios[0].goal);
+    /// ```
+    pub fn parse(input: &str) -> Result<Vec<Scenario>, SearchError> {
+        let mut scenarios = vec![];
+        for (index, line) in input.lines().enumerate() {
+            let line_number = index + 1;
+            if line.trim().is_empty() || (index == 0 && line.starts_with("version")) {
+                continue;
+            }
+            let fields: Vec<&str> = line.split('\t').map(|field| field.trim()).collect();
+            if fields.len() != 9 {
+                return Err(SearchError::InvalidFormat {
+                    line: line_number,
+                    message: format!("expected 9 fields, got {}", fields.len()),
+                });
+            }
+            let number = |field: &str| {
+                field
+                    .parse::<u32>()
+                    .map_err(|_| SearchError::InvalidFormat {
+                        line: line_number,
+                        message: format!("invalid number {:?}", field),
+                    })
+            };
+            scenarios.push(Scenario {
+                bucket: number(fields[0])?,
+                map: fields[1].to_string(),
+                width: number(fields[2])?,
+                height: number(fields[3])?,
+                start: Position::new(number(fields[5])?, number(fields[4])?),
+                goal: Position::new(number(fields[7])?, number(fields[6])?),
+                optimal_length: fields[8].parse().map_err(|_| SearchError::InvalidFormat {
+                    line: line_number,
+                    message: format!("invalid length {:?}", fields[8]),
+                })?,
+            });
+        }
+        Ok(scenarios)
+    }
+
+    /// Returns a problem from the scenario start to the scenario goal
+    /// on the nodes of `Graph::get_path_nodes`
+    pub fn get_problem(&self, graph: &Graph) -> Result<Problem, SearchError> {
+        self.check_bounds(graph)?;
+        Problem::new(
+            graph.get_path_nodes(),
+            graph.get_node_id_at_position(self.start),
+            graph.get_node_id_at_position(self.goal),
+            graph.size,
+        )
+    }
+
+    /// Runs a search for each scenario on the graph
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - The graph of the scenario map
+    /// * `scenarios` - The scenarios to run
+    /// * `search` - The search, e.g. `AStarSearch::search`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_problem_search::a_star_search::AStarSearch;
+    /// use rust_problem_search::graph::Graph;
+    /// use rust_problem_search::moving_ai::Scenario;
+    ///
+    /// let graph = Graph::from_moving_ai("type octile\nheight 2\nwidth 3\nmap\n...\n...\n").unwrap();
+    /// let scenarios = Scenario::parse("version 1\n0\tm.map\t3\t2\t0\t0\t2\t1\t2.41421356\n").unwrap();
+    ///
+    /// let outcomes = Scenario::run_all(&graph, &scenarios, AStarSearch::search).unwrap();
+    ///
+    /// assert!(outcomes.iter().all(|outcome| outcome.is_optimal()));
+    /// ```
+    pub fn run_all<F>(
+        graph: &Graph,
+        scenarios: &[Scenario],
+        search: F,
+    ) -> Result<Vec<ScenarioOutcome>, SearchError>
+    where
+        F: Fn(&Problem) -> Result<Option<SearchResult>, SearchError>,
+    {
+        let mut outcomes = Vec::with_capacity(scenarios.len());
+        let mut problem: Option<Problem> = None;
+
+        for scenario in scenarios.iter() {
+            scenario.check_bounds(graph)?;
+            let problem = match problem.as_mut() {
+                Some(problem) => {
+                    problem.start = graph.get_node_id_at_position(scenario.start);
+                    problem.goal = graph.get_node_id_at_position(scenario.goal);
+                    problem
+                }
+                None => problem.insert(scenario.get_problem(graph)?),
+            };
+            let result = search(problem)?;
+            outcomes.push(ScenarioOutcome {
+                scenario: scenario.clone(),
+                length: result.as_ref().map(|result| result.cost),
+                expanded: result.map_or(0, |result| result.expanded),
+            });
+        }
+        Ok(outcomes)
+    }
+
+    fn check_bounds(&self, graph: &Graph) -> Result<(), SearchError> {
+        for position in [self.start, self.goal].iter() {
+            if !graph.contains(*position) {
+                return Err(SearchError::PositionOutOfBounds(*position));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Graph {
+    /// Returns a graph read from a Moving AI `.map` file
+    ///
+    /// The passable terrain `.`, `G` and the swamp `S` become path tiles,
+    /// all other terrain becomes ground tiles. The graph is eight-connected
+    /// without corner cutting, like the optimal lengths of the scenarios.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The content of the map file
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_problem_search::graph::Graph;
+    ///
+    /// let input = "type octile\nheight 2\nwidth 3\nmap\n..@\nT..\n";
+    /// let graph = Graph::from_moving_ai(input).unwrap();
+    ///
+    /// assert_eq!(vec![1, 1, 2, 2, 1, 1], graph.tiles);
+    /// ```
+    pub fn from_moving_ai(input: &str) -> Result<Graph, SearchError> {
+        let mut lines = input.lines().enumerate();
+        let mut height = None;
+        let mut width = None;
+        let mut map_line = 0;
+
+        for (index, line) in lines.by_ref() {
+            let mut fields = line.split_whitespace();
+            match (fields.next(), fields.next()) {
+                (Some("type"), _) => {}
+                (Some("height"), Some(value)) => height = value.parse::<u32>().ok(),
+                (Some("width"), Some(value)) => width = value.parse::<u32>().ok(),
+                (Some("map"), None) => {
+                    map_line = index + 1;
+                    break;
+                }
+                _ => {
+                    return Err(SearchError::InvalidFormat {
+                        line: index + 1,
+                        message: format!("unexpected header {:?}", line),
+                    })
+                }
+            }
+        }
+
+        let (height, width) = match (height, width) {
+            (Some(height), Some(width)) => (height, width),
+            _ => {
+                return Err(SearchError::InvalidFormat {
+                    line: 1,
+                    message: "missing height or width".to_string(),
+                })
+            }
+        };
+        let size = width
+            .checked_mul(height)
+            .ok_or_else(|| SearchError::InvalidFormat {
+                line: map_line,
+                message: format!("{} * {} tiles are too many", width, height),
+            })?;
+
+        // a corrupt header may claim more tiles than the input holds
+        let mut tiles = Vec::with_capacity((size as usize).min(input.len()));
+        for row in 0..height as usize {
+            let (index, line) = lines.next().ok_or_else(|| SearchError::InvalidFormat {
+                line: map_line + row + 1,
+                message: format!("expected {} rows, got {}", height, row),
+            })?;
+            let line = line.trim_end();
+            if line.chars().count() != width as usize {
+                return Err(SearchError::InvalidFormat {
+                    line: index + 1,
+                    message: format!("expected {} tiles, got {}", width, line.chars().count()),
+                });
+            }
+            tiles.extend(line.chars().map(|character| match character {
+                '.' | 'G' | 'S' => Tile::Path as u8,
+                _ => Tile::Ground as u8,
+            }));
+        }
+
+        Ok(Graph::new(tiles, width, height)?
+            .with_connectivity(Connectivity::EightWithoutCornerCutting))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::a_star_search::AStarSearch;
+    use crate::uniform_cost_search::UniformCostSearch;
+
+    const MAP: &str = "type octile
+height 5
+width 6
+map
+......
+..@@T.
+....S.
+.@.@@.
+...@W.
+";
+
+    const SCENARIOS: &str = "version 1
+0\ttest.map\t6\t5\t0\t0\t5\t0\t5.00000000
+0\ttest.map\t6\t5\t0\t4\t2\t2\t4.00000000
+1\ttest.map\t6\t5\t0\t0\t3\t2\t4.41421356
+1\ttest.map\t6\t5\t0\t0\t5\t4\t8.41421356
+";
+
+    #[test]
+    fn from_moving_ai_returns_graph() {
+        let graph = Graph::from_moving_ai(MAP).unwrap();
+
+        assert_eq!(6, graph.width);
+        assert_eq!(5, graph.height);
+        assert_eq!(Connectivity::EightWithoutCornerCutting, graph.connectivity);
+        assert_eq!(
+            vec![
+                1, 1, 1, 1, 1, 1,
+                1, 1, 2, 2, 2, 1,
+                1, 1, 1, 1, 1, 1,
+                1, 2, 1, 2, 2, 1,
+                1, 1, 1, 2, 2, 1,
+            ],
+            graph.tiles
+        );
+    }
+
+    #[test]
+    fn from_moving_ai_with_short_row_returns_error() {
+        let input = "type octile\nheight 2\nwidth 3\nmap\n...\n..\n";
+
+        assert_eq!(
+            Some(SearchError::InvalidFormat {
+                line: 6,
+                message: "expected 3 tiles, got 2".to_string()
+            }),
+            Graph::from_moving_ai(input).err()
+        );
+    }
+
+    #[test]
+    fn from_moving_ai_with_missing_row_returns_error() {
+        let input = "type octile\nheight 3\nwidth 3\nmap\n...\n...\n";
+
+        assert_eq!(
+            Some(SearchError::InvalidFormat {
+                line: 7,
+                message: "expected 3 rows, got 2".to_string()
+            }),
+            Graph::from_moving_ai(input).err()
+        );
+    }
+
+    #[test]
+    fn from_moving_ai_with_too_large_size_returns_error() {
+        let input = "type octile\nheight 4294967295\nwidth 4294967295\nmap\n";
+
+        assert_eq!(
+            Some(SearchError::InvalidFormat {
+                line: 4,
+                message: "4294967295 * 4294967295 tiles are too many".to_string()
+            }),
+            Graph::from_moving_ai(input).err()
+        );
+    }
+
+    #[test]
+    fn from_moving_ai_without_size_returns_error() {
+        assert_eq!(
+            Some(SearchError::InvalidFormat {
+                line: 1,
+                message: "missing height or width".to_string()
+            }),
+            Graph::from_moving_ai("type octile\nmap\n").err()
+        );
+    }
+
+    #[test]
+    fn parse_returns_scenarios() {
+        let scenarios = Scenario::parse(SCENARIOS).unwrap();
+
+        assert_eq!(4, scenarios.len());
+        assert_eq!(
+            Scenario {
+                bucket: 1,
+                map: "test.map".to_string(),
+                width: 6,
+                height: 5,
+                start: Position::new(0, 0),
+                goal: Position::new(2, 3),
+                optimal_length: 4.41421356,
+            },
+            scenarios[2]
+        );
+    }
+
+    #[test]
+    fn parse_with_missing_field_returns_error() {
+        assert_eq!(
+            Some(SearchError::InvalidFormat {
+                line: 2,
+                message: "expected 9 fields, got 8".to_string()
+            }),
+            Scenario::parse("version 1\n0\tm.map\t3\t2\t0\t0\t2\t1\n").err()
+        );
+    }
+
+    #[test]
+    fn get_problem_outside_of_graph_returns_error() {
+        let graph = Graph::from_moving_ai(MAP).unwrap();
+        let scenarios = Scenario::parse("0\tm.map\t9\t9\t0\t0\t8\t8\t1.0\n").unwrap();
+
+        assert_eq!(
+            Some(SearchError::PositionOutOfBounds(Position::new(8, 8))),
+            scenarios[0].get_problem(&graph).err()
+        );
+    }
+
+    #[test]
+    fn run_all_with_a_star_search_returns_optimal_lengths() {
+        let graph = Graph::from_moving_ai(MAP).unwrap();
+        let scenarios = Scenario::parse(SCENARIOS).unwrap();
+        let outcomes = Scenario::run_all(&graph, &scenarios, AStarSearch::search).unwrap();
+
+        assert_eq!(4, outcomes.len());
+        assert!(outcomes.iter().all(|outcome| outcome.is_optimal()));
+    }
+
+    #[test]
+    fn run_all_with_uniform_cost_search_returns_optimal_lengths() {
+        let graph = Graph::from_moving_ai(MAP).unwrap();
+        let scenarios = Scenario::parse(SCENARIOS).unwrap();
+        let outcomes = Scenario::run_all(&graph, &scenarios, UniformCostSearch::search).unwrap();
+
+        assert!(outcomes.iter().all(|outcome| outcome.is_optimal()));
+    }
+
+    #[test]
+    fn is_optimal_with_longer_length_returns_false() {
+        let scenario = Scenario::parse(SCENARIOS).unwrap().remove(0);
+        let outcome = ScenarioOutcome {
+            scenario,
+            length: Some(5.5),
+            expanded: 12,
+        };
+
+        assert!(!outcome.is_optimal());
+    }
+
+    #[test]
+    fn is_optimal_without_path_returns_false() {
+        let scenario = Scenario::parse(SCENARIOS).unwrap().remove(0);
+        let outcome = ScenarioOutcome {
+            scenario,
+            length: None,
+            expanded: 0,
+        };
+
+        assert!(!outcome.is_optimal());
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::HashSet;
+
+use crate::node::{Node, NodeId};
+use crate::position::Position;
+use crate::search_error::SearchError;
+use crate::search_problem::SearchProblem;
+use crate::tile::Tile;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Defines which nodes end a path
+pub enum Goal {
+    /// Any of the nodes
+    Nodes(HashSet<NodeId>),
+    /// Any walkable node with a tile of this type
+    Tile(Tile),
+}
+
+#[derive(Debug, Clone)]
+/// A problem with several starts and several goals,
+/// solved by the path from any start to any goal
+pub struct MultiProblem {
+    pub nodes: Vec<Node>,
+    pub starts: Vec<NodeId>,
+    pub goal: Goal,
+    pub size: usize,
+}
+
+impl MultiProblem {
+    /// Returns a new problem
+    ///
+    /// # Arguments
+    ///
+    /// * `nodes` - A Vec<Node> indexed by NodeId
+    /// * `starts` - The NodeIds where the path may start
+    /// * `goal` - The goal nodes or the goal tile type
+    /// * `size` - The number of tiles in the graph
+    ///
+    /// Returns an error if there is no start or goal node, or one is unknown or not walkable
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_problem_search::graph::Graph;
+    /// use rust_problem_search::multi_problem::{Goal, MultiProblem};
+    /// use rust_problem_search::node::NodeId;
+    /// use rust_problem_search::tile::Tile;
+    ///
+    /// let graph = Graph::new(vec![1, 1, 0, 1, 1, 0], 3, 2).unwrap();
+    ///
+    /// let problem = MultiProblem::new(
+    ///     graph.get_possible_nodes(),
+    ///     vec![NodeId(0), NodeId(4)],
+    ///     Goal::Tile(Tile::None),
+    ///     graph.size,
+    /// ).unwrap();
+    /// ```
+    pub fn new(
+        nodes: Vec<Node>,
+        starts: Vec<NodeId>,
+        goal: Goal,
+        size: usize,
+    ) -> Result<Self, SearchError> {
+        let problem = MultiProblem {
+            nodes,
+            starts,
+            goal,
+            size,
+        };
+        problem.validate()?;
+        Ok(problem)
+    }
+
+    /// Returns the position of a node
+    pub fn get_position(&self, id: NodeId) -> Position {
+        self.nodes[id.index()].position
+    }
+
+    /// Checks that there are starts and goal nodes which are known and walkable
+    pub fn validate(&self) -> Result<(), SearchError> {
+        if self.starts.is_empty() {
+            return Err(SearchError::NoStart);
+        }
+        for start in &self.starts {
+            let node = self
+                .nodes
+                .get(start.index())
+                .ok_or(SearchError::UnknownStart(*start))?;
+            if !node.walkable {
+                return Err(SearchError::StartNotWalkable(node.position));
+            }
+        }
+        if let Goal::Nodes(goals) = &self.goal {
+            if goals.is_empty() {
+                return Err(SearchError::NoGoal);
+            }
+            for goal in goals {
+                let node = self
+                    .nodes
+                    .get(goal.index())
+                    .ok_or(SearchError::UnknownGoal(*goal))?;
+                if !node.walkable {
+                    return Err(SearchError::GoalNotWalkable(node.position));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The states are the nodes and one state before all starts, None,
+/// which reaches each start without cost
+impl SearchProblem for MultiProblem {
+    type State = Option<NodeId>;
+
+    fn initial_state(&self) -> Option<NodeId> {
+        None
+    }
+
+    fn is_goal(&self, state: &Option<NodeId>) -> bool {
+        match (state, &self.goal) {
+            (None, _) => false,
+            (Some(id), Goal::Nodes(goals)) => goals.contains(id),
+            (Some(id), Goal::Tile(tile)) => {
+                let node = &self.nodes[id.index()];
+                node.walkable && node.value == *tile
+            }
+        }
+    }
+
+    /// Returns the starts for the state before them,
+    /// the neighbours with the cost to enter them for a node
+    fn successors(&self, state: &Option<NodeId>) -> Vec<(Option<NodeId>, f64)> {
+        match state {
+            None => self
+                .starts
+                .iter()
+                .map(|start| (Some(*start), 0.0))
+                .collect(),
+            Some(id) => {
+                let node = &self.nodes[id.index()];
+                node.neighbours
+                    .iter()
+                    .filter_map(|next| {
+                        Some((Some(*next), self.nodes[next.index()].cost_from(node)?))
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    fn state_index(&self, state: &Option<NodeId>) -> Option<usize> {
+        Some(state.map_or(0, |id| id.index() + 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Graph;
+
+    fn graph() -> Graph {
+        Graph::new(vec![1, 1, 0, 2, 1, 1], 3, 2).unwrap()
+    }
+
+    #[test]
+    fn new_without_start_returns_error() {
+        let goals = [NodeId(1)].iter().copied().collect();
+        let problem = MultiProblem::new(graph().get_path_nodes(), vec![], Goal::Nodes(goals), 6);
+
+        assert_eq!(SearchError::NoStart, problem.unwrap_err());
+    }
+
+    #[test]
+    fn new_with_empty_goal_nodes_returns_error() {
+        let problem = MultiProblem::new(
+            graph().get_path_nodes(),
+            vec![NodeId(0)],
+            Goal::Nodes(HashSet::new()),
+            6,
+        );
+
+        assert_eq!(SearchError::NoGoal, problem.unwrap_err());
+    }
+
+    #[test]
+    fn new_with_goal_on_ground_returns_error() {
+        let goals = [NodeId(1), NodeId(3)].iter().copied().collect();
+        let problem = MultiProblem::new(
+            graph().get_path_nodes(),
+            vec![NodeId(0)],
+            Goal::Nodes(goals),
+            6,
+        );
+
+        assert_eq!(
+            SearchError::GoalNotWalkable(Position::new(1, 0)),
+            problem.unwrap_err()
+        );
+    }
+
+    #[test]
+    fn successors_of_initial_state_returns_starts() {
+        let problem = MultiProblem::new(
+            graph().get_possible_nodes(),
+            vec![NodeId(0), NodeId(5)],
+            Goal::Tile(Tile::None),
+            6,
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![(Some(NodeId(0)), 0.0), (Some(NodeId(5)), 0.0)],
+            problem.successors(&None)
+        );
+        assert!(problem.is_goal(&Some(NodeId(2))));
+        assert!(!problem.is_goal(&Some(NodeId(1))));
+    }
+}
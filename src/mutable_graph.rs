@@ -0,0 +1,285 @@
+use std::collections::HashSet;
+
+use crate::graph::Graph;
+use crate::node::Node;
+use crate::position::Position;
+use crate::problem::{Problem, ProblemView};
+use crate::search_error::SearchError;
+use crate::tile::Tile;
+
+#[derive(Debug, Clone)]
+/// A graph together with its nodes, which are kept in sync when tiles change
+///
+/// Only the nodes around a changed tile are rebuilt, instead of all nodes
+/// like `get_path_nodes` and `get_possible_nodes` do.
+///
+/// # Example
+///
+/// ```
+/// use rust_problem_search::breath_first_search::BreathFirstSearch;
+/// use rust_problem_search::graph::Graph;
+/// use rust_problem_search::mutable_graph::MutableGraph;
+/// use rust_problem_search::position::Position;
+/// use rust_problem_search::tile::Tile;
+///
+/// let graph = Graph::new(vec![1; 9], 3, 3).unwrap();
+/// let mut mutable_graph = MutableGraph::with_path_nodes(graph);
+/// mutable_graph.set_tile(Position::new(0, 1), Tile::Ground).unwrap();
+///
+/// let problem = mutable_graph
+///     .get_problem(Position::new(0, 0), Position::new(0, 2))
+///     .unwrap();
+///
+/// assert_eq!(4, BreathFirstSearch::search(&problem).unwrap().unwrap().length);
+/// ```
+pub struct MutableGraph {
+    graph: Graph,
+    tiles: HashSet<Tile>,
+    nodes: Vec<Node>,
+}
+
+impl MutableGraph {
+    /// Returns a mutable graph whose nodes are the ones of `Graph::get_path_nodes`
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - The graph to edit
+    pub fn with_path_nodes(graph: Graph) -> Self {
+        MutableGraph::with_tiles(graph, &[Tile::Path])
+    }
+
+    /// Returns a mutable graph whose nodes are the ones of `Graph::get_possible_nodes`
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - The graph to edit
+    pub fn with_possible_nodes(graph: Graph) -> Self {
+        MutableGraph::with_tiles(graph, &[Tile::Path, Tile::None])
+    }
+
+    fn with_tiles(graph: Graph, tiles: &[Tile]) -> Self {
+        let tiles: HashSet<Tile> = tiles.iter().copied().collect();
+        let nodes = graph.get_neighbours_with_tile(&tiles);
+        MutableGraph {
+            graph,
+            tiles,
+            nodes,
+        }
+    }
+
+    /// Returns the graph with all tile changes applied
+    pub fn get_graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    /// Returns the nodes of the graph, indexed by NodeId
+    pub fn get_nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// Sets the tile at a position and rebuilds the nodes around it
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The position of the tile
+    /// * `tile` - The new tile
+    ///
+    /// Returns an error if the position is outside of the graph
+    pub fn set_tile(&mut self, position: Position, tile: Tile) -> Result<(), SearchError> {
+        self.set_tiles(&[(position, tile)])
+    }
+
+    /// Sets several tiles at once and rebuilds each node around them once,
+    /// none of the tiles is set if a position is outside of the graph
+    ///
+    /// # Arguments
+    ///
+    /// * `tiles` - The positions with their new tiles
+    pub fn set_tiles(&mut self, tiles: &[(Position, Tile)]) -> Result<(), SearchError> {
+        self.graph.set_tiles(tiles)?;
+        let positions: Vec<Position> = tiles.iter().map(|(position, _)| *position).collect();
+        self.graph
+            .update_nodes_with_tile(&mut self.nodes, &positions, &self.tiles);
+        Ok(())
+    }
+
+    /// Returns a problem on a copy of the current nodes,
+    /// see `get_problem_view` to search without copying them
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The position where the path starts
+    /// * `goal` - The position where the path ends
+    ///
+    /// Returns an error if the start or the goal is outside of the graph or not walkable
+    pub fn get_problem(&self, start: Position, goal: Position) -> Result<Problem, SearchError> {
+        self.get_problem_view(start, goal)?;
+        Problem::new(
+            self.nodes.clone(),
+            self.graph.get_node_id_at_position(start),
+            self.graph.get_node_id_at_position(goal),
+            self.graph.size,
+        )
+    }
+
+    /// Returns a problem which borrows the current nodes, for the generic searches
+    /// like `AStarSearch::search_problem`
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The position where the path starts
+    /// * `goal` - The position where the path ends
+    ///
+    /// Returns an error if the start or the goal is outside of the graph or not walkable
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_problem_search::a_star_search::AStarSearch;
+    /// use rust_problem_search::graph::Graph;
+    /// use rust_problem_search::mutable_graph::MutableGraph;
+    /// use rust_problem_search::position::Position;
+    /// use rust_problem_search::tile::Tile;
+    ///
+    /// let graph = Graph::new(vec![1; 9], 3, 3).unwrap();
+    /// let mut mutable_graph = MutableGraph::with_path_nodes(graph);
+    /// mutable_graph.set_tile(Position::new(0, 1), Tile::Ground).unwrap();
+    ///
+    /// let problem = mutable_graph
+    ///     .get_problem_view(Position::new(0, 0), Position::new(0, 2))
+    ///     .unwrap();
+    /// let result = AStarSearch::search_problem(&problem).unwrap();
+    ///
+    /// assert_eq!(Position::new(1, 1), problem.get_position(result.path[2]));
+    /// ```
+    pub fn get_problem_view(
+        &self,
+        start: Position,
+        goal: Position,
+    ) -> Result<ProblemView<'_>, SearchError> {
+        for position in [start, goal] {
+            if !self.graph.contains(position) {
+                return Err(SearchError::PositionOutOfBounds(position));
+            }
+        }
+        ProblemView::new(
+            &self.nodes,
+            self.graph.get_node_id_at_position(start),
+            self.graph.get_node_id_at_position(goal),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::a_star_search::AStarSearch;
+    use crate::connectivity::Connectivity;
+
+    fn changes() -> Vec<(Position, Tile)> {
+        vec![
+            (Position::new(1, 1), Tile::Ground),
+            (Position::new(0, 3), Tile::None),
+            (Position::new(2, 2), Tile::Ground),
+            (Position::new(3, 0), Tile::Path),
+            (Position::new(1, 1), Tile::None),
+        ]
+    }
+
+    #[test]
+    fn set_tile_keeps_nodes_in_sync() {
+        for connectivity in [
+            Connectivity::Four,
+            Connectivity::Eight,
+            Connectivity::EightWithoutCornerCutting,
+        ] {
+            let graph = Graph::new(vec![1, 0, 2, 1, 1, 1, 0, 1, 2, 1, 1, 1, 0, 0, 1, 1], 4, 4)
+                .unwrap()
+                .with_connectivity(connectivity);
+            let mut path_graph = MutableGraph::with_path_nodes(graph.clone());
+            let mut possible_graph = MutableGraph::with_possible_nodes(graph);
+
+            for (position, tile) in changes() {
+                path_graph.set_tile(position, tile).unwrap();
+                possible_graph.set_tile(position, tile).unwrap();
+
+                assert_eq!(
+                    path_graph.get_graph().get_path_nodes(),
+                    path_graph.get_nodes()
+                );
+                assert_eq!(
+                    possible_graph.get_graph().get_possible_nodes(),
+                    possible_graph.get_nodes()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn set_tiles_sets_all_tiles() {
+        let graph = Graph::new(vec![1; 16], 4, 4)
+            .unwrap()
+            .with_connectivity(Connectivity::EightWithoutCornerCutting);
+        let mut mutable_graph = MutableGraph::with_possible_nodes(graph);
+        mutable_graph.set_tiles(&changes()).unwrap();
+
+        assert_eq!(
+            vec![1, 1, 1, 0, 1, 0, 1, 1, 1, 1, 2, 1, 1, 1, 1, 1],
+            mutable_graph.get_graph().tiles
+        );
+        assert_eq!(
+            mutable_graph.get_graph().get_possible_nodes(),
+            mutable_graph.get_nodes()
+        );
+    }
+
+    #[test]
+    fn set_tiles_outside_of_graph_sets_no_tile() {
+        let mut mutable_graph =
+            MutableGraph::with_path_nodes(Graph::new(vec![1; 4], 2, 2).unwrap());
+
+        assert_eq!(
+            Err(SearchError::PositionOutOfBounds(Position::new(2, 0))),
+            mutable_graph.set_tiles(&[
+                (Position::new(0, 0), Tile::Ground),
+                (Position::new(2, 0), Tile::Ground)
+            ])
+        );
+        assert_eq!(vec![1; 4], mutable_graph.get_graph().tiles);
+    }
+
+    #[test]
+    fn get_problem_view_returns_same_path_as_get_problem() {
+        let graph = Graph::new(vec![1, 0, 2, 1, 1, 1, 0, 1, 2, 1, 1, 1, 0, 0, 1, 1], 4, 4)
+            .unwrap()
+            .with_connectivity(Connectivity::EightWithoutCornerCutting);
+        let mut mutable_graph = MutableGraph::with_possible_nodes(graph);
+        mutable_graph.set_tiles(&changes()).unwrap();
+        let (start, goal) = (Position::new(0, 0), Position::new(3, 3));
+
+        let problem = mutable_graph.get_problem(start, goal).unwrap();
+        let problem_view = mutable_graph.get_problem_view(start, goal).unwrap();
+        let result = AStarSearch::search(&problem).unwrap().unwrap();
+        let view_result = AStarSearch::search_problem(&problem_view)
+            .unwrap()
+            .map(|id| problem_view.get_position(id));
+
+        assert_eq!(result, view_result);
+    }
+
+    #[test]
+    fn get_problem_with_ground_goal_returns_error() {
+        let mut mutable_graph =
+            MutableGraph::with_path_nodes(Graph::new(vec![1; 4], 2, 2).unwrap());
+        mutable_graph
+            .set_tile(Position::new(1, 1), Tile::Ground)
+            .unwrap();
+
+        assert_eq!(
+            SearchError::GoalNotWalkable(Position::new(1, 1)),
+            mutable_graph
+                .get_problem(Position::new(0, 0), Position::new(1, 1))
+                .unwrap_err()
+        );
+    }
+}
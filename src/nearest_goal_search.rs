@@ -0,0 +1,181 @@
+use crate::multi_problem::MultiProblem;
+use crate::search_error::SearchError;
+use crate::search_result::{NearestGoalResult, SearchResult};
+use crate::uniform_cost_search::UniformCostSearch;
+
+#[derive(Debug, Clone)]
+pub struct NearestGoalSearch {}
+
+impl NearestGoalSearch {
+    /// Returns an Option with the cheapest path from any start to any goal,
+    /// the start and the goal it connects and the search statistics,
+    /// or an error if the problem is invalid
+    ///
+    /// All starts are expanded together like a single start, so a single uniform cost search
+    /// replaces one search for each pair of a start and a goal.
+    ///
+    /// # Arguments
+    ///
+    /// * `problem` The problem contains the starts, the goals and the nodes where to find the path
+    ///
+    /// # Example
+    /// ```
+    /// use rust_problem_search::graph::Graph;
+    /// use rust_problem_search::multi_problem::{Goal, MultiProblem};
+    /// use rust_problem_search::nearest_goal_search::NearestGoalSearch;
+    /// use rust_problem_search::position::Position;
+    /// use rust_problem_search::tile::Tile;
+    ///
+    /// let graph = Graph::new(vec![1, 1, 1, 1, 1, 0], 6, 1).unwrap();
+    /// let starts = vec![
+    ///     graph.get_node_id_at_position(Position::new(0, 0)),
+    ///     graph.get_node_id_at_position(Position::new(0, 2)),
+    /// ];
+    /// let problem =
+    ///     MultiProblem::new(graph.get_possible_nodes(), starts, Goal::Tile(Tile::None), graph.size)
+    ///         .unwrap();
+    ///
+    /// let result = NearestGoalSearch::search(&problem).unwrap().unwrap();
+    ///
+    /// assert_eq!(Position::new(0, 2), result.start);
+    /// assert_eq!(Position::new(0, 5), result.goal);
+    /// ```
+    pub fn search(problem: &MultiProblem) -> Result<Option<NearestGoalResult>, SearchError> {
+        problem.validate()?;
+        Ok(UniformCostSearch::search_problem(problem).map(|result| {
+            // drops the state before the starts
+            let path: Vec<_> = result
+                .path
+                .into_iter()
+                .flatten()
+                .map(|id| problem.get_position(id))
+                .collect();
+            NearestGoalResult {
+                start: path[0],
+                goal: path[path.len() - 1],
+                result: SearchResult {
+                    length: path.len() - 1,
+                    path,
+                    cost: result.cost,
+                    expanded: result.expanded.saturating_sub(1),
+                    frontier_peak: result.frontier_peak,
+                },
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Graph;
+    use crate::multi_problem::Goal;
+    use crate::node::NodeId;
+    use crate::position::Position;
+    use crate::problem::Problem;
+    use crate::tile::Tile;
+
+    fn map() -> Graph {
+        let map = "
+..........
+.####.###.
+.#   .#  .
+.####.####
+..........
+";
+        Graph::from_ascii(map).unwrap().graph
+    }
+
+    fn ids(graph: &Graph, positions: &[Position]) -> Vec<NodeId> {
+        positions
+            .iter()
+            .map(|position| graph.get_node_id_at_position(*position))
+            .collect()
+    }
+
+    #[test]
+    fn search_returns_nearest_pair_of_start_and_goal() {
+        let graph = map();
+        let starts = [
+            Position::new(0, 0),
+            Position::new(4, 9),
+            Position::new(2, 5),
+        ];
+        let goals = [Position::new(0, 9), Position::new(4, 0)];
+        let problem = MultiProblem::new(
+            graph.get_path_nodes(),
+            ids(&graph, &starts),
+            Goal::Nodes(ids(&graph, &goals).into_iter().collect()),
+            graph.size,
+        )
+        .unwrap();
+        let result = NearestGoalSearch::search(&problem).unwrap().unwrap();
+
+        let mut cheapest = f64::INFINITY;
+        for start in &starts {
+            for goal in &goals {
+                let pair_problem = Problem::new(
+                    graph.get_path_nodes(),
+                    graph.get_node_id_at_position(*start),
+                    graph.get_node_id_at_position(*goal),
+                    graph.size,
+                )
+                .unwrap();
+                let pair_result = UniformCostSearch::search(&pair_problem).unwrap().unwrap();
+                cheapest = cheapest.min(pair_result.cost);
+            }
+        }
+        assert_eq!(cheapest, result.result.cost);
+        assert_eq!(Position::new(0, 0), result.start);
+        assert_eq!(Position::new(4, 0), result.goal);
+        assert_eq!(result.start, result.result.path[0]);
+        assert_eq!(result.goal, result.result.path[result.result.length]);
+    }
+
+    #[test]
+    fn search_with_goal_tile_returns_nearest_tile() {
+        let graph = map();
+        let problem = MultiProblem::new(
+            graph.get_possible_nodes(),
+            ids(&graph, &[Position::new(4, 9), Position::new(0, 0)]),
+            Goal::Tile(Tile::None),
+            graph.size,
+        )
+        .unwrap();
+        let result = NearestGoalSearch::search(&problem).unwrap().unwrap();
+
+        assert_eq!(Position::new(4, 9), result.start);
+        assert_eq!(Position::new(2, 4), result.goal);
+        assert_eq!(9.0, result.result.cost);
+    }
+
+    #[test]
+    fn search_with_start_on_goal_returns_single_position() {
+        let graph = map();
+        let problem = MultiProblem::new(
+            graph.get_path_nodes(),
+            ids(&graph, &[Position::new(0, 0), Position::new(4, 0)]),
+            Goal::Nodes(ids(&graph, &[Position::new(4, 0)]).into_iter().collect()),
+            graph.size,
+        )
+        .unwrap();
+        let result = NearestGoalSearch::search(&problem).unwrap().unwrap();
+
+        assert_eq!(vec![Position::new(4, 0)], result.result.path);
+        assert_eq!(0.0, result.result.cost);
+    }
+
+    #[test]
+    fn search_without_goal_tile_returns_none() {
+        let graph = map();
+        let problem = MultiProblem::new(
+            graph.get_path_nodes(),
+            ids(&graph, &[Position::new(0, 0)]),
+            Goal::Tile(Tile::None),
+            graph.size,
+        )
+        .unwrap();
+
+        assert_eq!(None, NearestGoalSearch::search(&problem).unwrap());
+    }
+}
@@ -1,12 +1,56 @@
+use std::convert::TryFrom;
+use std::f64::consts::SQRT_2;
+use std::fmt;
+
 use crate::position::Position;
 use crate::tile::Tile;
 
+#[derive(Hash, Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone)]
+/// Identifies a node inside the graph by its index in the tile vec
+pub struct NodeId(pub u32);
+
+impl NodeId {
+    /// Returns a new NodeId
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - An usize defines the index in the tile vec
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index does not fit into an u32, `Graph::new` rejects graphs with more tiles
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::rust_problem_search::node::NodeId;
+    ///
+    /// let id = NodeId::new(4);
+    /// assert_eq!(4, id.index());
+    /// ```
+    pub fn new(index: usize) -> Self {
+        NodeId(u32::try_from(index).expect("node index does not fit into an u32"))
+    }
+
+    /// Returns the index in the tile vec
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl fmt::Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
 #[derive(Hash, Eq, PartialEq, Debug, Clone)]
 /// Defines a node inside the graph
 pub struct Node {
     pub position: Position,
     pub value: Tile,
-    pub neighbours: Vec<String>,
+    pub walkable: bool,
+    pub neighbours: Vec<NodeId>,
 }
 
 impl Node {
@@ -16,7 +60,8 @@ impl Node {
     ///
     /// * `position` - A Position defines the position
     /// * `value` - A Tile defines type of this node
-    /// * `neighbours` - A Vec<String> with the connected node keys
+    /// * `walkable` - A bool defines if a path may start, end or lead through this node
+    /// * `neighbours` - A Vec<NodeId> with the connected nodes
     ///
     /// # Example
     ///
@@ -26,16 +71,32 @@ impl Node {
     /// use crate::rust_problem_search::tile::Tile;
     ///
     /// let position = Position::new(1, 0);
-    /// let neighbours: Vec<String> = vec![];
-    /// let node = Node::new(position, Tile::Path, Vec::new());
+    /// let node = Node::new(position, Tile::Path, true, Vec::new());
     /// ```
-    pub fn new(position: Position, value: Tile, neighbours: Vec<String>) -> Self {
+    pub fn new(position: Position, value: Tile, walkable: bool, neighbours: Vec<NodeId>) -> Self {
         Node {
             position,
             value,
+            walkable,
             neighbours,
         }
     }
+
+    /// Returns the cost to enter this node from a neighbour,
+    /// the tile cost multiplied by the square root of two for a diagonal step,
+    /// or None if the tile is never walkable
+    ///
+    /// # Arguments
+    ///
+    /// * `previous` - The neighbour the step starts from
+    pub fn cost_from(&self, previous: &Node) -> Option<f64> {
+        let cost = f64::from(self.value.cost()?);
+        if self.position.is_diagonal_to(&previous.position) {
+            Some(cost * SQRT_2)
+        } else {
+            Some(cost)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -45,11 +106,53 @@ mod tests {
     #[test]
     fn new_returns_new_node() {
         let position = Position::new(1, 0);
-        let neighbours: Vec<String> = vec![];
-        let node = Node::new(position, Tile::Path, Vec::new());
+        let neighbours: Vec<NodeId> = vec![];
+        let node = Node::new(position, Tile::Path, true, Vec::new());
 
         assert_eq!(position, node.position);
         assert_eq!(Tile::Path, node.value);
+        assert!(node.walkable);
         assert_eq!(neighbours, node.neighbours);
     }
+
+    #[test]
+    fn cost_from_returns_tile_cost() {
+        let previous = Node::new(Position::new(1, 0), Tile::Path, true, vec![]);
+        let node = Node::new(Position::new(1, 1), Tile::None, true, vec![]);
+
+        assert_eq!(Some(3.0), node.cost_from(&previous));
+    }
+
+    #[test]
+    fn cost_from_diagonal_neighbour_returns_scaled_tile_cost() {
+        let previous = Node::new(Position::new(0, 0), Tile::Path, true, vec![]);
+        let node = Node::new(Position::new(1, 1), Tile::Path, true, vec![]);
+
+        assert_eq!(Some(SQRT_2), node.cost_from(&previous));
+    }
+
+    #[test]
+    fn cost_from_ground_returns_none() {
+        let previous = Node::new(Position::new(1, 0), Tile::Path, true, vec![]);
+        let node = Node::new(Position::new(1, 1), Tile::Ground, false, vec![]);
+
+        assert_eq!(None, node.cost_from(&previous));
+    }
+
+    #[test]
+    fn node_id_index_returns_index() {
+        assert_eq!(4, NodeId::new(4).index());
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    #[should_panic(expected = "node index does not fit into an u32")]
+    fn node_id_new_with_too_large_index_panics() {
+        NodeId::new(u32::MAX as usize + 1);
+    }
+
+    #[test]
+    fn node_id_display_prints_formatted_id() {
+        assert_eq!("#4".to_string(), format!("{}", NodeId(4)));
+    }
 }
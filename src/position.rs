@@ -1,5 +1,9 @@
 use std::cmp;
+use std::f64::consts::SQRT_2;
 use std::fmt;
+use std::str::FromStr;
+
+use crate::search_error::SearchError;
 
 #[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
 /// Defines a position, based on row and column
@@ -49,6 +53,58 @@ impl Position {
             cmp::max(self.column, position.column) - cmp::min(self.column, position.column);
         cmp::max(row, column) + cmp::min(row, column)
     }
+
+    /// Returns the octile distance between two positions,
+    /// where a diagonal step costs the square root of two
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - a position
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::rust_problem_search::position::Position;
+    ///
+    /// let position_a = Position::new(5, 3);
+    /// let position_b = Position::new(2, 7);
+    ///
+    /// position_b.octile_distance(&position_a);
+    /// ```
+    pub fn octile_distance(&self, position: &Position) -> f64 {
+        let row = cmp::max(self.row, position.row) - cmp::min(self.row, position.row);
+        let column =
+            cmp::max(self.column, position.column) - cmp::min(self.column, position.column);
+        let straight = cmp::max(row, column) - cmp::min(row, column);
+        straight as f64 + cmp::min(row, column) as f64 * SQRT_2
+    }
+
+    /// Returns the straight line distance between the centres of two positions
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - a position
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::rust_problem_search::position::Position;
+    ///
+    /// let position_a = Position::new(5, 3);
+    /// let position_b = Position::new(2, 7);
+    ///
+    /// assert_eq!(5.0, position_b.euclidean_distance(&position_a));
+    /// ```
+    pub fn euclidean_distance(&self, position: &Position) -> f64 {
+        let row = f64::from(self.row) - f64::from(position.row);
+        let column = f64::from(self.column) - f64::from(position.column);
+        row.hypot(column)
+    }
+
+    /// Returns true if the positions differ in row and column
+    pub fn is_diagonal_to(&self, position: &Position) -> bool {
+        self.row != position.row && self.column != position.column
+    }
 }
 
 impl fmt::Display for Position {
@@ -57,6 +113,28 @@ impl fmt::Display for Position {
     }
 }
 
+impl FromStr for Position {
+    type Err = SearchError;
+
+    /// Parses a position from the `row-col` format of its Display implementation
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::rust_problem_search::position::Position;
+    ///
+    /// assert_eq!(Position::new(3, 12), "3-12".parse().unwrap());
+    /// ```
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || SearchError::InvalidPosition(value.to_string());
+        let (row, column) = value.split_once('-').ok_or_else(invalid)?;
+        Ok(Position::new(
+            row.trim().parse().map_err(|_| invalid())?,
+            column.trim().parse().map_err(|_| invalid())?,
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -84,4 +162,46 @@ mod tests {
 
         assert_eq!(7, position_b.distance(&position_a));
     }
+
+    #[test]
+    fn octile_distance_returns_octile_distance() {
+        let position_a = Position::new(5, 3);
+        let position_b = Position::new(2, 7);
+
+        assert!((1.0 + 3.0 * SQRT_2 - position_b.octile_distance(&position_a)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn euclidean_distance_returns_straight_line_distance() {
+        let position_a = Position::new(5, 3);
+        let position_b = Position::new(2, 7);
+
+        assert_eq!(5.0, position_b.euclidean_distance(&position_a));
+        assert_eq!(0.0, position_a.euclidean_distance(&position_a));
+    }
+
+    #[test]
+    fn is_diagonal_to_returns_true_for_different_row_and_column() {
+        assert!(Position::new(1, 1).is_diagonal_to(&Position::new(2, 2)));
+        assert!(!Position::new(1, 1).is_diagonal_to(&Position::new(1, 2)));
+    }
+
+    #[test]
+    fn from_str_parses_displayed_position() {
+        let position = Position::new(14, 3);
+
+        assert_eq!(Ok(position), format!("{}", position).parse());
+    }
+
+    #[test]
+    fn from_str_with_invalid_value_returns_error() {
+        assert_eq!(
+            Err(SearchError::InvalidPosition("1,2".to_string())),
+            "1,2".parse::<Position>()
+        );
+        assert_eq!(
+            Err(SearchError::InvalidPosition("1--2".to_string())),
+            "1--2".parse::<Position>()
+        );
+    }
 }
@@ -1,32 +1,403 @@
-use crate::node::Node;
-use std::collections::HashMap;
+use crate::node::{Node, NodeId};
+use crate::position::Position;
+use crate::search_error::SearchError;
+use crate::search_problem::SearchProblem;
 
 #[derive(Debug, Clone)]
 pub struct Problem {
-    pub nodes: HashMap<String, Node>,
-    pub start: String,
-    pub goal: String,
+    /// Private so the nodes do not change behind `diagonal_moves`
+    nodes: Vec<Node>,
+    pub start: NodeId,
+    pub goal: NodeId,
     pub size: usize,
+    /// Whether any node has a diagonal neighbour, computed once in `new`
+    diagonal_moves: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+/// A problem on borrowed nodes, e.g. the nodes of a `MutableGraph`,
+/// which is searched without copying the nodes
+///
+/// The generic searches like `AStarSearch::search_problem` take it like a `Problem`
+/// and return a path of NodeIds.
+pub struct ProblemView<'a> {
+    nodes: &'a [Node],
+    pub start: NodeId,
+    pub goal: NodeId,
+    diagonal_moves: bool,
 }
 
 impl Problem {
-    pub fn new(nodes: HashMap<String, Node>, start: String, goal: String, size: usize) -> Self {
-        Problem { nodes, start, goal, size }
+    /// Returns a new problem
+    ///
+    /// # Arguments
+    ///
+    /// * `nodes` - A Vec<Node> indexed by NodeId
+    /// * `start` - The NodeId where the path starts
+    /// * `goal` - The NodeId where the path ends
+    /// * `size` - The number of tiles in the graph
+    ///
+    /// Returns an error if the start or the goal is unknown or not walkable
+    pub fn new(
+        nodes: Vec<Node>,
+        start: NodeId,
+        goal: NodeId,
+        size: usize,
+    ) -> Result<Self, SearchError> {
+        validate(&nodes, start, goal)?;
+        let diagonal_moves = has_diagonal_moves(&nodes);
+        Ok(Problem {
+            nodes,
+            start,
+            goal,
+            size,
+            diagonal_moves,
+        })
+    }
+
+    /// Returns the nodes indexed by NodeId
+    pub fn get_nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// Returns the position of a node
+    pub fn get_position(&self, id: NodeId) -> Position {
+        self.nodes[id.index()].position
+    }
+
+    /// Returns true if any node has a diagonal neighbour,
+    /// then the heuristic is the octile distance instead of the manhattan distance
+    pub fn has_diagonal_moves(&self) -> bool {
+        self.diagonal_moves
+    }
+
+    /// Returns the problem on the borrowed nodes of this problem
+    pub fn as_view(&self) -> ProblemView<'_> {
+        ProblemView {
+            nodes: &self.nodes,
+            start: self.start,
+            goal: self.goal,
+            diagonal_moves: self.diagonal_moves,
+        }
+    }
+
+    /// Returns the distance between a node and the goal which never overestimates the cost,
+    /// the manhattan distance or the octile distance if the nodes have diagonal neighbours
+    pub(crate) fn get_goal_distance(&self, id: NodeId) -> f64 {
+        self.as_view().get_goal_distance(id)
+    }
+
+    /// Returns the number of nodes reachable from the start and the highest cost of a path
+    /// without loops through them, or None if the goal is not reachable
+    ///
+    /// No path without loops takes more steps than the reachable nodes minus one,
+    /// or costs more than entering each of them with its most expensive step.
+    pub(crate) fn get_reachable_bound(&self) -> Option<(usize, f64)> {
+        let mut entry_costs: Vec<Option<f64>> = vec![None; self.nodes.len()];
+        let mut stack = vec![self.start];
+        entry_costs[self.start.index()] = Some(0.0);
+        while let Some(id) = stack.pop() {
+            for (next, step_cost) in self.successors(&id) {
+                let entry_cost = &mut entry_costs[next.index()];
+                if entry_cost.is_none() {
+                    stack.push(next);
+                }
+                *entry_cost = Some(entry_cost.map_or(step_cost, |cost| cost.max(step_cost)));
+            }
+        }
+        entry_costs[self.goal.index()]?;
+
+        // the start is never entered again on a path without loops
+        entry_costs[self.start.index()] = Some(0.0);
+        let reachable = entry_costs.iter().flatten().count();
+        Some((reachable, entry_costs.iter().flatten().sum()))
+    }
+
+    /// Checks that the start and the goal are known and walkable nodes
+    pub fn validate(&self) -> Result<(), SearchError> {
+        validate(&self.nodes, self.start, self.goal)
+    }
+}
+
+impl<'a> ProblemView<'a> {
+    /// Returns a new problem on borrowed nodes
+    ///
+    /// # Arguments
+    ///
+    /// * `nodes` - The nodes indexed by NodeId
+    /// * `start` - The NodeId where the path starts
+    /// * `goal` - The NodeId where the path ends
+    ///
+    /// Returns an error if the start or the goal is unknown or not walkable
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_problem_search::a_star_search::AStarSearch;
+    /// use rust_problem_search::graph::Graph;
+    /// use rust_problem_search::node::NodeId;
+    /// use rust_problem_search::problem::ProblemView;
+    ///
+    /// let graph = Graph::new(vec![1, 1, 1], 3, 1).unwrap();
+    /// let nodes = graph.get_path_nodes();
+    ///
+    /// let problem = ProblemView::new(&nodes, NodeId(0), NodeId(2)).unwrap();
+    /// let result = AStarSearch::search_problem(&problem).unwrap();
+    ///
+    /// assert_eq!(vec![NodeId(0), NodeId(1), NodeId(2)], result.path);
+    /// ```
+    pub fn new(nodes: &'a [Node], start: NodeId, goal: NodeId) -> Result<Self, SearchError> {
+        validate(nodes, start, goal)?;
+        Ok(ProblemView {
+            nodes,
+            start,
+            goal,
+            diagonal_moves: has_diagonal_moves(nodes),
+        })
+    }
+
+    /// Returns the borrowed nodes indexed by NodeId
+    pub fn get_nodes(&self) -> &'a [Node] {
+        self.nodes
+    }
+
+    /// Returns the position of a node
+    pub fn get_position(&self, id: NodeId) -> Position {
+        self.nodes[id.index()].position
+    }
+
+    fn get_goal_distance(&self, id: NodeId) -> f64 {
+        let position = self.get_position(id);
+        let goal = self.get_position(self.goal);
+        if self.diagonal_moves {
+            position.octile_distance(&goal)
+        } else {
+            f64::from(position.distance(&goal))
+        }
+    }
+}
+
+fn has_diagonal_moves(nodes: &[Node]) -> bool {
+    nodes.iter().any(|node| {
+        node.neighbours.iter().any(|id| {
+            nodes
+                .get(id.index())
+                .is_some_and(|neighbour| neighbour.position.is_diagonal_to(&node.position))
+        })
+    })
+}
+
+fn validate(nodes: &[Node], start: NodeId, goal: NodeId) -> Result<(), SearchError> {
+    let start = nodes
+        .get(start.index())
+        .ok_or(SearchError::UnknownStart(start))?;
+    let goal = nodes
+        .get(goal.index())
+        .ok_or(SearchError::UnknownGoal(goal))?;
+    if !start.walkable {
+        return Err(SearchError::StartNotWalkable(start.position));
+    }
+    if !goal.walkable {
+        return Err(SearchError::GoalNotWalkable(goal.position));
+    }
+    Ok(())
+}
+
+impl SearchProblem for Problem {
+    type State = NodeId;
+
+    fn initial_state(&self) -> NodeId {
+        self.start
+    }
+
+    fn is_goal(&self, state: &NodeId) -> bool {
+        *state == self.goal
+    }
+
+    /// Returns the neighbours with the cost to enter them, tiles which are never walkable are skipped
+    fn successors(&self, state: &NodeId) -> Vec<(NodeId, f64)> {
+        self.as_view().successors(state)
+    }
+
+    /// Returns the manhattan distance to the goal, or the octile distance
+    /// if the nodes have diagonal neighbours, like the A* search
+    fn heuristic(&self, state: &NodeId) -> f64 {
+        self.get_goal_distance(*state)
+    }
+
+    fn state_index(&self, state: &NodeId) -> Option<usize> {
+        Some(state.index())
+    }
+}
+
+impl SearchProblem for ProblemView<'_> {
+    type State = NodeId;
+
+    fn initial_state(&self) -> NodeId {
+        self.start
+    }
+
+    fn is_goal(&self, state: &NodeId) -> bool {
+        *state == self.goal
+    }
+
+    /// Returns the neighbours with the cost to enter them, tiles which are never walkable are skipped
+    fn successors(&self, state: &NodeId) -> Vec<(NodeId, f64)> {
+        let node = &self.nodes[state.index()];
+        node.neighbours
+            .iter()
+            .filter_map(|id| Some((*id, self.nodes[id.index()].cost_from(node)?)))
+            .collect()
+    }
+
+    /// Returns the manhattan distance to the goal, or the octile distance
+    /// if the nodes have diagonal neighbours, like the A* search
+    fn heuristic(&self, state: &NodeId) -> f64 {
+        self.get_goal_distance(*state)
+    }
+
+    fn state_index(&self, state: &NodeId) -> Option<usize> {
+        Some(state.index())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::connectivity::Connectivity;
+    use crate::graph::Graph;
+    use crate::tile::Tile;
+
+    fn nodes() -> Vec<Node> {
+        vec![
+            Node::new(Position::new(0, 0), Tile::Path, true, vec![NodeId(1)]),
+            Node::new(Position::new(0, 1), Tile::Path, true, vec![NodeId(0)]),
+            Node::new(Position::new(0, 2), Tile::Ground, false, vec![]),
+        ]
+    }
 
     #[test]
     fn new_with_nodes_and_size_returns_position() {
-        let nodes = HashMap::new();
-        let problem = Problem::new(nodes.clone(), "1-1".to_string(), "1-2".to_string(), 10);
+        let problem = Problem::new(nodes(), NodeId(0), NodeId(1), 3).unwrap();
+
+        assert_eq!(nodes(), problem.get_nodes());
+        assert_eq!(NodeId(0), problem.start);
+        assert_eq!(NodeId(1), problem.goal);
+        assert_eq!(3, problem.size);
+    }
+
+    #[test]
+    fn new_with_unknown_start_returns_error() {
+        assert_eq!(
+            Some(SearchError::UnknownStart(NodeId(7))),
+            Problem::new(nodes(), NodeId(7), NodeId(1), 3).err()
+        );
+    }
+
+    #[test]
+    fn new_with_unknown_goal_returns_error() {
+        assert_eq!(
+            Some(SearchError::UnknownGoal(NodeId(3))),
+            Problem::new(nodes(), NodeId(0), NodeId(3), 3).err()
+        );
+    }
+
+    #[test]
+    fn new_with_start_on_ground_returns_error() {
+        assert_eq!(
+            Some(SearchError::StartNotWalkable(Position::new(0, 2))),
+            Problem::new(nodes(), NodeId(2), NodeId(1), 3).err()
+        );
+    }
+
+    #[test]
+    fn new_with_goal_on_ground_returns_error() {
+        assert_eq!(
+            Some(SearchError::GoalNotWalkable(Position::new(0, 2))),
+            Problem::new(nodes(), NodeId(0), NodeId(2), 3).err()
+        );
+    }
+
+    #[test]
+    fn has_diagonal_moves_returns_false_for_straight_neighbours() {
+        let problem = Problem::new(nodes(), NodeId(0), NodeId(1), 3).unwrap();
+
+        assert!(!problem.has_diagonal_moves());
+    }
+
+    #[test]
+    fn has_diagonal_moves_returns_true_for_diagonal_neighbours() {
+        let nodes = vec![
+            Node::new(Position::new(0, 0), Tile::Path, true, vec![NodeId(1)]),
+            Node::new(Position::new(1, 1), Tile::Path, true, vec![NodeId(0)]),
+        ];
+        let problem = Problem::new(nodes, NodeId(0), NodeId(1), 4).unwrap();
+
+        assert!(problem.has_diagonal_moves());
+    }
+
+    #[test]
+    fn validate_with_changed_start_returns_error() {
+        let mut problem = Problem::new(nodes(), NodeId(0), NodeId(1), 3).unwrap();
+        problem.start = NodeId(9);
+
+        assert_eq!(
+            Err(SearchError::UnknownStart(NodeId(9))),
+            problem.validate()
+        );
+    }
+
+    #[test]
+    fn successors_returns_neighbours_with_cost() {
+        let nodes = vec![
+            Node::new(
+                Position::new(0, 0),
+                Tile::Path,
+                true,
+                vec![NodeId(1), NodeId(2)],
+            ),
+            Node::new(Position::new(1, 1), Tile::None, true, vec![NodeId(0)]),
+            Node::new(Position::new(0, 1), Tile::Ground, false, vec![]),
+        ];
+        let problem = Problem::new(nodes, NodeId(0), NodeId(1), 4).unwrap();
+
+        assert_eq!(
+            vec![(NodeId(1), 3.0 * std::f64::consts::SQRT_2)],
+            problem.successors(&NodeId(0))
+        );
+        assert!(problem.is_goal(&NodeId(1)));
+        assert_eq!(0.0, problem.heuristic(&NodeId(1)));
+    }
+
+    #[test]
+    fn get_reachable_bound_returns_nodes_and_cost_of_longest_path() {
+        let graph = Graph::new(vec![1, 0, 1, 2, 1], 5, 1).unwrap();
+        let mut problem =
+            Problem::new(graph.get_possible_nodes(), NodeId(0), NodeId(2), 5).unwrap();
+
+        assert_eq!(Some((3, 4.0)), problem.get_reachable_bound());
+        problem.goal = NodeId(4);
+        assert_eq!(None, problem.get_reachable_bound());
+    }
+
+    #[test]
+    fn heuristic_without_diagonal_moves_returns_manhattan_distance() {
+        let graph = Graph::new(vec![1; 9], 3, 3).unwrap();
+        let problem = Problem::new(graph.get_path_nodes(), NodeId(0), NodeId(8), 9).unwrap();
+
+        assert_eq!(4.0, problem.heuristic(&NodeId(0)));
+    }
+
+    #[test]
+    fn heuristic_with_diagonal_moves_returns_octile_distance() {
+        let graph = Graph::new(vec![1; 9], 3, 3)
+            .unwrap()
+            .with_connectivity(Connectivity::Eight);
+        let problem = Problem::new(graph.get_path_nodes(), NodeId(0), NodeId(8), 9).unwrap();
 
-        assert_eq!(nodes.clone(), problem.nodes);
-        assert_eq!("1-1".to_string(), problem.start);
-        assert_eq!("1-2".to_string(), problem.goal);
-        assert_eq!(10, problem.size);
+        assert_eq!(
+            2.0 * std::f64::consts::SQRT_2,
+            problem.heuristic(&NodeId(0))
+        );
     }
 }
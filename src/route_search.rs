@@ -0,0 +1,196 @@
+use crate::a_star_search::AStarSearch;
+use crate::graph::Graph;
+use crate::node::Node;
+use crate::position::Position;
+use crate::problem::Problem;
+use crate::search_error::SearchError;
+use crate::search_result::RouteResult;
+
+#[derive(Debug, Clone)]
+pub struct RouteSearch {}
+
+impl RouteSearch {
+    /// Returns the cheapest path which visits the waypoints in their order,
+    /// or an error if a waypoint is invalid or a leg between two waypoints has no path
+    ///
+    /// Each leg is searched with the A* search and the legs are joined without repeating
+    /// the waypoint between them. A closed patrol route repeats the first waypoint at the end.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` The graph the nodes are build from
+    /// * `nodes` The nodes where to find the path, e.g. `graph.get_path_nodes()`
+    /// * `waypoints` The ordered positions the path has to visit
+    ///
+    /// # Example
+    /// ```
+    /// use rust_problem_search::graph::Graph;
+    /// use rust_problem_search::position::Position;
+    /// use rust_problem_search::route_search::RouteSearch;
+    ///
+    /// let graph = Graph::new(vec![1; 9], 3, 3).unwrap();
+    /// let waypoints = vec![
+    ///     Position::new(0, 0),
+    ///     Position::new(0, 2),
+    ///     Position::new(2, 2),
+    /// ];
+    ///
+    /// let result = RouteSearch::search(&graph, graph.get_path_nodes(), &waypoints).unwrap();
+    ///
+    /// assert_eq!(4, result.length);
+    /// assert_eq!(vec![2.0, 2.0], result.leg_costs);
+    /// ```
+    pub fn search(
+        graph: &Graph,
+        nodes: Vec<Node>,
+        waypoints: &[Position],
+    ) -> Result<RouteResult, SearchError> {
+        if nodes.len() != graph.size {
+            return Err(SearchError::InvalidTileCount {
+                expected: graph.size,
+                actual: nodes.len(),
+            });
+        }
+        let first = *waypoints.first().ok_or(SearchError::NoStart)?;
+        if let Some(position) = waypoints
+            .iter()
+            .find(|position| !graph.contains(**position))
+        {
+            return Err(SearchError::PositionOutOfBounds(*position));
+        }
+
+        let first_id = graph.get_node_id_at_position(first);
+        let mut problem = Problem::new(nodes, first_id, first_id, graph.size)?;
+        let mut route = RouteResult {
+            path: vec![first],
+            length: 0,
+            cost: 0.0,
+            leg_costs: Vec::with_capacity(waypoints.len() - 1),
+            expanded: 0,
+            frontier_peak: 0,
+        };
+
+        for (leg, pair) in waypoints.windows(2).enumerate() {
+            problem.start = graph.get_node_id_at_position(pair[0]);
+            problem.goal = graph.get_node_id_at_position(pair[1]);
+            let result = AStarSearch::search(&problem)?.ok_or(SearchError::UnreachableLeg {
+                leg,
+                from: pair[0],
+                to: pair[1],
+            })?;
+            route.path.extend(result.path.into_iter().skip(1));
+            route.cost += result.cost;
+            route.leg_costs.push(result.cost);
+            route.expanded += result.expanded;
+            route.frontier_peak = route.frontier_peak.max(result.frontier_peak);
+        }
+        route.length = route.path.len() - 1;
+        Ok(route)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map() -> &'static str {
+        "
+..........
+.####.###.
+.#   .#  .
+.####.####
+..........
+"
+    }
+
+    #[test]
+    fn search_returns_joined_path_through_waypoints() {
+        let graph = Graph::from_ascii(map()).unwrap().graph;
+        let waypoints = vec![
+            Position::new(0, 0),
+            Position::new(4, 9),
+            Position::new(2, 4),
+            Position::new(0, 0),
+        ];
+        let result = RouteSearch::search(&graph, graph.get_possible_nodes(), &waypoints).unwrap();
+
+        let mut leg_costs = vec![];
+        for pair in waypoints.windows(2) {
+            let problem = Problem::new(
+                graph.get_possible_nodes(),
+                graph.get_node_id_at_position(pair[0]),
+                graph.get_node_id_at_position(pair[1]),
+                graph.size,
+            )
+            .unwrap();
+            leg_costs.push(AStarSearch::search(&problem).unwrap().unwrap().cost);
+        }
+        assert_eq!(leg_costs, result.leg_costs);
+        assert_eq!(leg_costs.iter().sum::<f64>(), result.cost);
+        assert_eq!(result.length + 1, result.path.len());
+        assert_eq!(Position::new(0, 0), result.path[0]);
+        assert_eq!(Position::new(0, 0), result.path[result.length]);
+        assert!(result.path.contains(&Position::new(4, 9)));
+        assert!(result.path.contains(&Position::new(2, 4)));
+        assert!(result
+            .path
+            .windows(2)
+            .all(|step| step[0].distance(&step[1]) == 1));
+    }
+
+    #[test]
+    fn search_with_unreachable_leg_returns_error() {
+        let graph = Graph::from_ascii("...\n###\n...").unwrap().graph;
+        let waypoints = vec![
+            Position::new(0, 0),
+            Position::new(0, 2),
+            Position::new(2, 0),
+        ];
+
+        assert_eq!(
+            Err(SearchError::UnreachableLeg {
+                leg: 1,
+                from: Position::new(0, 2),
+                to: Position::new(2, 0),
+            }),
+            RouteSearch::search(&graph, graph.get_path_nodes(), &waypoints)
+        );
+    }
+
+    #[test]
+    fn search_with_single_waypoint_returns_single_position() {
+        let graph = Graph::new(vec![1; 4], 2, 2).unwrap();
+        let result =
+            RouteSearch::search(&graph, graph.get_path_nodes(), &[Position::new(1, 1)]).unwrap();
+
+        assert_eq!(vec![Position::new(1, 1)], result.path);
+        assert_eq!(0.0, result.cost);
+        assert!(result.leg_costs.is_empty());
+    }
+
+    #[test]
+    fn search_with_invalid_waypoints_returns_error() {
+        let graph = Graph::new(vec![1, 1, 1, 2], 2, 2).unwrap();
+
+        assert_eq!(
+            Err(SearchError::NoStart),
+            RouteSearch::search(&graph, graph.get_path_nodes(), &[])
+        );
+        assert_eq!(
+            Err(SearchError::PositionOutOfBounds(Position::new(0, 2))),
+            RouteSearch::search(
+                &graph,
+                graph.get_path_nodes(),
+                &[Position::new(0, 0), Position::new(0, 2)]
+            )
+        );
+        assert_eq!(
+            Err(SearchError::GoalNotWalkable(Position::new(1, 1))),
+            RouteSearch::search(
+                &graph,
+                graph.get_path_nodes(),
+                &[Position::new(0, 0), Position::new(1, 1)]
+            )
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::node::NodeId;
+use crate::position::Position;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Defines why a graph or a problem is invalid
+pub enum SearchError {
+    /// The start node id is not part of the nodes
+    UnknownStart(NodeId),
+    /// The goal node id is not part of the nodes
+    UnknownGoal(NodeId),
+    /// The start is on a tile which is not walkable
+    StartNotWalkable(Position),
+    /// The goal is on a tile which is not walkable
+    GoalNotWalkable(Position),
+    /// The number of tiles does not match width * height
+    InvalidTileCount { expected: usize, actual: usize },
+    /// A map contains a character which is no tile or marker
+    InvalidCharacter { position: Position, character: char },
+    /// A map row is longer or shorter than the first row
+    InvalidRowLength {
+        row: u32,
+        expected: usize,
+        actual: usize,
+    },
+    /// A map has no start marker
+    MissingStart,
+    /// A map has no goal marker
+    MissingGoal,
+    /// A map has a second start marker
+    DuplicateStart(Position),
+    /// A map has a second goal marker
+    DuplicateGoal(Position),
+    /// A position is outside of the graph
+    PositionOutOfBounds(Position),
+    /// A map or scenario file can not be parsed
+    InvalidFormat { line: usize, message: String },
+    /// A text is no position in the `row-col` format
+    InvalidPosition(String),
+    /// A heuristic weight is negative or not finite
+    InvalidWeight(f64),
+    /// A problem has an empty list of starts
+    NoStart,
+    /// A problem has an empty list of goals
+    NoGoal,
+    /// A route has no path between two consecutive waypoints,
+    /// `leg` is 0 for the leg from the first to the second waypoint
+    UnreachableLeg {
+        leg: usize,
+        from: Position,
+        to: Position,
+    },
+    /// A target of a tour has no path from the start
+    UnreachableTarget(Position),
+}
+
+impl fmt::Display for SearchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SearchError::UnknownStart(id) => write!(f, "unknown start node {}", id),
+            SearchError::UnknownGoal(id) => write!(f, "unknown goal node {}", id),
+            SearchError::StartNotWalkable(position) => {
+                write!(f, "start {} is not walkable", position)
+            }
+            SearchError::GoalNotWalkable(position) => {
+                write!(f, "goal {} is not walkable", position)
+            }
+            SearchError::InvalidTileCount { expected, actual } => write!(
+                f,
+                "expected {} tiles for width * height, got {}",
+                expected, actual
+            ),
+            SearchError::InvalidCharacter {
+                position,
+                character,
+            } => write!(f, "invalid character {:?} at {}", character, position),
+            SearchError::InvalidRowLength {
+                row,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "expected {} tiles in row {}, got {}",
+                expected, row, actual
+            ),
+            SearchError::MissingStart => write!(f, "missing start marker"),
+            SearchError::MissingGoal => write!(f, "missing goal marker"),
+            SearchError::DuplicateStart(position) => {
+                write!(f, "duplicate start marker at {}", position)
+            }
+            SearchError::DuplicateGoal(position) => {
+                write!(f, "duplicate goal marker at {}", position)
+            }
+            SearchError::PositionOutOfBounds(position) => {
+                write!(f, "position {} is outside of the graph", position)
+            }
+            SearchError::InvalidFormat { line, message } => {
+                write!(f, "invalid format in line {}: {}", line, message)
+            }
+            SearchError::InvalidPosition(value) => {
+                write!(f, "invalid position {:?}, expected row-col", value)
+            }
+            SearchError::InvalidWeight(weight) => {
+                write!(
+                    f,
+                    "invalid weight {}, expected a finite number >= 0",
+                    weight
+                )
+            }
+            SearchError::NoStart => write!(f, "no start given"),
+            SearchError::NoGoal => write!(f, "no goal given"),
+            SearchError::UnreachableLeg { leg, from, to } => {
+                write!(f, "leg {} from {} to {} is unreachable", leg, from, to)
+            }
+            SearchError::UnreachableTarget(position) => {
+                write!(f, "target {} is unreachable", position)
+            }
+        }
+    }
+}
+
+impl Error for SearchError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_prints_unknown_start() {
+        assert_eq!(
+            "unknown start node #12".to_string(),
+            format!("{}", SearchError::UnknownStart(NodeId(12)))
+        );
+    }
+
+    #[test]
+    fn display_prints_goal_not_walkable() {
+        assert_eq!(
+            "goal 1-2 is not walkable".to_string(),
+            format!("{}", SearchError::GoalNotWalkable(Position::new(1, 2)))
+        );
+    }
+
+    #[test]
+    fn display_prints_invalid_tile_count() {
+        assert_eq!(
+            "expected 9 tiles for width * height, got 8".to_string(),
+            format!(
+                "{}",
+                SearchError::InvalidTileCount {
+                    expected: 9,
+                    actual: 8
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn display_prints_invalid_character() {
+        assert_eq!(
+            "invalid character 'x' at 1-2".to_string(),
+            format!(
+                "{}",
+                SearchError::InvalidCharacter {
+                    position: Position::new(1, 2),
+                    character: 'x'
+                }
+            )
+        );
+    }
+}
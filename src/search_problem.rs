@@ -0,0 +1,261 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Defines a problem the searches can solve, independent of a grid
+///
+/// # Example
+///
+/// ```
+/// use rust_problem_search::breath_first_search::BreathFirstSearch;
+/// use rust_problem_search::search_problem::SearchProblem;
+///
+/// /// Reach a number from 1 by adding one or doubling
+/// struct Numbers {
+///     goal: u32,
+/// }
+///
+/// impl SearchProblem for Numbers {
+///     type State = u32;
+///
+///     fn initial_state(&self) -> u32 {
+///         1
+///     }
+///
+///     fn is_goal(&self, state: &u32) -> bool {
+///         *state == self.goal
+///     }
+///
+///     fn successors(&self, state: &u32) -> Vec<(u32, f64)> {
+///         vec![(state + 1, 1.0), (state * 2, 1.0)]
+///     }
+/// }
+///
+/// let result = BreathFirstSearch::search_problem(&Numbers { goal: 10 }).unwrap();
+///
+/// assert_eq!(vec![1, 2, 4, 5, 10], result.path);
+/// ```
+pub trait SearchProblem {
+    /// A state of the problem, e.g. a node of a graph or the board of a puzzle
+    type State: Clone + Eq + Hash;
+
+    /// Returns the state where the search starts
+    fn initial_state(&self) -> Self::State;
+
+    /// Returns true if the state solves the problem
+    fn is_goal(&self, state: &Self::State) -> bool;
+
+    /// Returns the states reachable in one step together with the cost of the step
+    fn successors(&self, state: &Self::State) -> Vec<(Self::State, f64)>;
+
+    /// Returns an estimate of the cost from the state to the nearest goal,
+    /// which must never overestimate the real cost
+    fn heuristic(&self, _state: &Self::State) -> f64 {
+        0.0
+    }
+
+    /// Returns a small unique index of the state if the problem has one,
+    /// e.g. the NodeId of a grid node, so the searches can store the reached states
+    /// in a vector instead of a hash map
+    fn state_index(&self, _state: &Self::State) -> Option<usize> {
+        None
+    }
+}
+
+/// The states reached by a search, each with its parent and the cost from the initial state
+pub(crate) struct SearchTree<'a, P: SearchProblem> {
+    problem: &'a P,
+    states: Vec<P::State>,
+    parents: Vec<Option<usize>>,
+    costs: Vec<f64>,
+    indices: HashMap<P::State, usize>,
+    dense_indices: Vec<Option<usize>>,
+}
+
+impl<'a, P: SearchProblem> SearchTree<'a, P> {
+    /// Returns a tree which only contains the initial state of the problem
+    pub(crate) fn new(problem: &'a P) -> Self {
+        let mut tree = SearchTree {
+            problem,
+            states: Vec::new(),
+            parents: Vec::new(),
+            costs: Vec::new(),
+            indices: HashMap::new(),
+            dense_indices: Vec::new(),
+        };
+        tree.insert(problem.initial_state(), None, 0.0);
+        tree
+    }
+
+    /// Returns the index of the state if it was reached before
+    pub(crate) fn get(&self, state: &P::State) -> Option<usize> {
+        match self.problem.state_index(state) {
+            Some(dense_index) => self.dense_indices.get(dense_index).copied().flatten(),
+            None => self.indices.get(state).copied(),
+        }
+    }
+
+    /// Adds a newly reached state and returns its index
+    pub(crate) fn insert(&mut self, state: P::State, parent: Option<usize>, cost: f64) -> usize {
+        let index = self.states.len();
+        match self.problem.state_index(&state) {
+            Some(dense_index) => {
+                if dense_index >= self.dense_indices.len() {
+                    self.dense_indices.resize(dense_index + 1, None);
+                }
+                self.dense_indices[dense_index] = Some(index);
+            }
+            None => {
+                self.indices.insert(state.clone(), index);
+            }
+        }
+        self.states.push(state);
+        self.parents.push(parent);
+        self.costs.push(cost);
+        index
+    }
+
+    /// Sets a cheaper parent for a reached state
+    pub(crate) fn update(&mut self, index: usize, parent: usize, cost: f64) {
+        self.parents[index] = Some(parent);
+        self.costs[index] = cost;
+    }
+
+    pub(crate) fn state(&self, index: usize) -> &P::State {
+        &self.states[index]
+    }
+
+    pub(crate) fn cost(&self, index: usize) -> f64 {
+        self.costs[index]
+    }
+
+    /// Returns the states from the initial state to the state at the index
+    pub(crate) fn path(&self, index: usize) -> Vec<P::State> {
+        let mut path = vec![self.states[index].clone()];
+        let mut next = self.parents[index];
+        while let Some(parent) = next {
+            path.push(self.states[parent].clone());
+            next = self.parents[parent];
+        }
+        path.reverse();
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::a_star_search::AStarSearch;
+    use crate::breath_first_search::BreathFirstSearch;
+    use crate::uniform_cost_search::UniformCostSearch;
+
+    /// The 3x3 sliding tile puzzle, 0 is the blank
+    struct SlidingTiles {
+        board: [u8; 9],
+    }
+
+    const SOLVED: [u8; 9] = [1, 2, 3, 4, 5, 6, 7, 8, 0];
+
+    impl SearchProblem for SlidingTiles {
+        type State = [u8; 9];
+
+        fn initial_state(&self) -> [u8; 9] {
+            self.board
+        }
+
+        fn is_goal(&self, state: &[u8; 9]) -> bool {
+            *state == SOLVED
+        }
+
+        fn successors(&self, state: &[u8; 9]) -> Vec<([u8; 9], f64)> {
+            let blank = state.iter().position(|tile| *tile == 0).unwrap();
+            let (row, column) = (blank / 3, blank % 3);
+            let mut moves = Vec::new();
+            if row > 0 {
+                moves.push(blank - 3);
+            }
+            if row < 2 {
+                moves.push(blank + 3);
+            }
+            if column > 0 {
+                moves.push(blank - 1);
+            }
+            if column < 2 {
+                moves.push(blank + 1);
+            }
+            moves
+                .into_iter()
+                .map(|tile| {
+                    let mut next = *state;
+                    next.swap(blank, tile);
+                    (next, 1.0)
+                })
+                .collect()
+        }
+
+        fn heuristic(&self, state: &[u8; 9]) -> f64 {
+            state
+                .iter()
+                .enumerate()
+                .filter(|(_, tile)| **tile != 0)
+                .map(|(index, tile)| {
+                    let target = usize::from(*tile) - 1;
+                    let rows = (index / 3) as i32 - (target / 3) as i32;
+                    let columns = (index % 3) as i32 - (target % 3) as i32;
+                    f64::from(rows.abs() + columns.abs())
+                })
+                .sum()
+        }
+    }
+
+    fn puzzle() -> SlidingTiles {
+        SlidingTiles {
+            board: [
+                4, 1, 3,
+                7, 2, 6,
+                0, 5, 8],
+        }
+    }
+
+    #[test]
+    fn breath_first_search_solves_sliding_tiles() {
+        let result = BreathFirstSearch::search_problem(&puzzle()).unwrap();
+
+        assert_eq!(6, result.length);
+        assert_eq!(6.0, result.cost);
+        assert_eq!(puzzle().board, result.path[0]);
+        assert_eq!(SOLVED, result.path[6]);
+    }
+
+    #[test]
+    fn a_star_search_solves_sliding_tiles_with_fewer_expansions() {
+        let a_star = AStarSearch::search_problem(&puzzle()).unwrap();
+        let uniform_cost = UniformCostSearch::search_problem(&puzzle()).unwrap();
+
+        assert_eq!(6.0, a_star.cost);
+        assert_eq!(6.0, uniform_cost.cost);
+        assert!(a_star.expanded < uniform_cost.expanded);
+    }
+
+    #[test]
+    fn search_problem_with_solved_state_returns_single_state() {
+        let result = BreathFirstSearch::search_problem(&SlidingTiles { board: SOLVED }).unwrap();
+
+        assert_eq!(vec![SOLVED], result.path);
+        assert_eq!(0, result.length);
+    }
+
+    #[test]
+    fn path_returns_states_from_initial_state() {
+        let problem = SlidingTiles { board: SOLVED };
+        let mut tree = SearchTree::new(&problem);
+        let (first, second, third) = ([0; 9], [1; 9], [2; 9]);
+        let first_index = tree.insert(first, Some(0), 1.0);
+        let second_index = tree.insert(second, Some(0), 5.0);
+        tree.update(second_index, first_index, 2.0);
+
+        assert_eq!(vec![SOLVED, first, second], tree.path(second_index));
+        assert_eq!(2.0, tree.cost(second_index));
+        assert_eq!(Some(second_index), tree.get(&second));
+        assert_eq!(None, tree.get(&third));
+    }
+}
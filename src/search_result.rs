@@ -0,0 +1,184 @@
+use crate::position::Position;
+use crate::search_problem::{SearchProblem, SearchTree};
+
+#[derive(Debug, Clone, PartialEq)]
+/// The path found by a search and some statistics about the search
+///
+/// The path holds positions for a grid `Problem` and states for any other `SearchProblem`.
+pub struct SearchResult<S = Position> {
+    /// The ordered positions from the start to the goal
+    pub path: Vec<S>,
+    /// The number of steps from the start to the goal
+    pub length: usize,
+    /// The sum of the costs to enter each tile on the path
+    pub cost: f64,
+    /// The number of nodes taken from the frontier and expanded
+    pub expanded: usize,
+    /// The largest number of nodes in the frontier at the same time
+    pub frontier_peak: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The result of a search which trades optimality for speed
+pub struct BoundedSearchResult<S = Position> {
+    pub result: SearchResult<S>,
+    /// The cost of the path is at most this factor times the optimal cost,
+    /// infinite if there is no guarantee
+    pub bound: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The result of a search from several starts to several goals
+pub struct NearestGoalResult {
+    /// The start the path begins at
+    pub start: Position,
+    /// The goal the path ends at, the nearest one to any start
+    pub goal: Position,
+    pub result: SearchResult,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The result of an any-angle search, the path leads in straight lines from waypoint to waypoint
+pub struct WaypointResult {
+    /// The ordered positions from the start to the goal where the path turns
+    pub waypoints: Vec<Position>,
+    /// The sum of the euclidean distances between the waypoints
+    pub length: f64,
+    /// The number of nodes taken from the frontier and expanded
+    pub expanded: usize,
+    /// The largest number of nodes in the frontier at the same time
+    pub frontier_peak: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The result of a route through several waypoints in their order
+pub struct RouteResult {
+    /// The ordered positions from the first to the last waypoint
+    pub path: Vec<Position>,
+    /// The number of steps from the first to the last waypoint
+    pub length: usize,
+    /// The sum of the costs of all legs
+    pub cost: f64,
+    /// The cost of each leg from one waypoint to the next
+    pub leg_costs: Vec<f64>,
+    /// The number of nodes expanded by the searches of all legs
+    pub expanded: usize,
+    /// The largest number of nodes in the frontier of any leg
+    pub frontier_peak: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The result of a tour from a start through targets in any order
+pub struct TourResult {
+    /// The targets in the order they are visited
+    pub order: Vec<Position>,
+    /// The path from the start through the targets in their order
+    pub route: RouteResult,
+    /// True if no other order is cheaper, false if the order is an approximation
+    pub optimal: bool,
+}
+
+impl<S> SearchResult<S> {
+    /// Builds the result by walking the parents back from a reached goal
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The states reached by the search
+    /// * `goal` - The index of the goal in the tree
+    /// * `expanded` - The number of expanded nodes
+    /// * `frontier_peak` - The largest frontier size
+    pub(crate) fn from_tree<P>(
+        tree: &SearchTree<P>,
+        goal: usize,
+        expanded: usize,
+        frontier_peak: usize,
+    ) -> Self
+    where
+        P: SearchProblem<State = S>,
+    {
+        let path = tree.path(goal);
+        SearchResult {
+            length: path.len() - 1,
+            path,
+            cost: tree.cost(goal),
+            expanded,
+            frontier_peak,
+        }
+    }
+
+    /// Returns the result with each step of the path converted
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Converts a step of the path, e.g. a NodeId to its Position
+    pub fn map<T, F>(self, f: F) -> SearchResult<T>
+    where
+        F: FnMut(S) -> T,
+    {
+        SearchResult {
+            path: self.path.into_iter().map(f).collect(),
+            length: self.length,
+            cost: self.cost,
+            expanded: self.expanded,
+            frontier_peak: self.frontier_peak,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Graph;
+    use crate::node::NodeId;
+    use crate::problem::Problem;
+
+    fn problem() -> Problem {
+        let tiles: Vec<u8> = vec![
+            1, 0, 2,
+            2, 1, 2,
+            2, 1, 2];
+        let graph = Graph::new(tiles, 3, 3).unwrap();
+        Problem::new(graph.get_possible_nodes(), NodeId(0), NodeId(7), graph.size).unwrap()
+    }
+
+    #[test]
+    fn from_tree_returns_ordered_path() {
+        let problem = problem();
+        let mut tree = SearchTree::new(&problem);
+        let second = tree.insert(NodeId(1), Some(0), 3.0);
+        let third = tree.insert(NodeId(4), Some(second), 4.0);
+        tree.insert(NodeId(3), Some(0), 1.0);
+        let goal = tree.insert(NodeId(7), Some(third), 5.0);
+        let result = SearchResult::from_tree(&tree, goal, 4, 2);
+
+        assert_eq!(
+            vec![NodeId(0), NodeId(1), NodeId(4), NodeId(7)],
+            result.path
+        );
+        assert_eq!(3, result.length);
+        assert_eq!(5.0, result.cost);
+        assert_eq!(4, result.expanded);
+        assert_eq!(2, result.frontier_peak);
+    }
+
+    #[test]
+    fn from_tree_with_start_equals_goal_returns_single_state() {
+        let problem = problem();
+        let tree = SearchTree::new(&problem);
+        let result = SearchResult::from_tree(&tree, 0, 0, 0);
+
+        assert_eq!(vec![NodeId(0)], result.path);
+        assert_eq!(0, result.length);
+        assert_eq!(0.0, result.cost);
+    }
+
+    #[test]
+    fn map_converts_path() {
+        let problem = problem();
+        let tree = SearchTree::new(&problem);
+        let result = SearchResult::from_tree(&tree, 0, 1, 1).map(|id| problem.get_position(id));
+
+        assert_eq!(vec![Position::new(0, 0)], result.path);
+        assert_eq!(1, result.expanded);
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::BinaryHeap;
+
+use crate::a_star_search::State;
+use crate::graph::Graph;
+use crate::node::NodeId;
+use crate::problem::Problem;
+use crate::search_error::SearchError;
+use crate::search_result::WaypointResult;
+
+#[derive(Debug, Clone)]
+pub struct ThetaStarSearch {}
+
+impl ThetaStarSearch {
+    /// Returns an Option with the shortest any-angle path and the search statistics,
+    /// or an error if the problem is invalid or does not belong to the graph
+    ///
+    /// The search works like the A* search on the neighbours of the nodes, but a reached node
+    /// takes the parent of the expanded node as its own parent if there is a line of sight
+    /// between both. The path leads in straight lines between the waypoints and its length
+    /// is the euclidean distance, the traversal costs of the tiles are not taken into account.
+    /// Steps to neighbours without a line of sight, like diagonal steps past a blocked corner
+    /// with `Connectivity::Eight`, are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` The graph the nodes of the problem are build from
+    /// * `problem` The problem contains the start, the end and the nodes where to find the path
+    ///
+    /// # Example
+    /// ```
+    /// use rust_problem_search::theta_star_search::ThetaStarSearch;
+    /// use rust_problem_search::graph::Graph;
+    /// use rust_problem_search::position::Position;
+    ///
+    /// let ascii_map = Graph::from_ascii("S....\n.....\n....G\n").unwrap();
+    /// let problem = ascii_map.get_path_problem().unwrap();
+    ///
+    /// let result = ThetaStarSearch::search(&ascii_map.graph, &problem).unwrap().unwrap();
+    ///
+    /// assert_eq!(vec![Position::new(0, 0), Position::new(2, 4)], result.waypoints);
+    /// ```
+    pub fn search(graph: &Graph, problem: &Problem) -> Result<Option<WaypointResult>, SearchError> {
+        problem.validate()?;
+        if problem.get_nodes().len() != graph.size {
+            return Err(SearchError::InvalidTileCount {
+                expected: graph.size,
+                actual: problem.get_nodes().len(),
+            });
+        }
+
+        let goal = problem.get_position(problem.goal);
+        let heuristic = |id: NodeId| problem.get_position(id).euclidean_distance(&goal);
+        let mut costs = vec![f64::INFINITY; problem.get_nodes().len()];
+        let mut parents: Vec<Option<NodeId>> = vec![None; problem.get_nodes().len()];
+        let mut closed = vec![false; problem.get_nodes().len()];
+        let mut frontier = BinaryHeap::new();
+        let mut expanded = 0;
+
+        costs[problem.start.index()] = 0.0;
+        frontier.push(State {
+            estimate: heuristic(problem.start),
+            cost: 0.0,
+            index: problem.start.index(),
+        });
+        let mut frontier_peak = frontier.len();
+
+        while let Some(State { cost, index, .. }) = frontier.pop() {
+            if closed[index] || cost > costs[index] {
+                continue;
+            }
+            closed[index] = true;
+            let id = NodeId::new(index);
+
+            if id == problem.goal {
+                return Ok(Some(ThetaStarSearch::get_result(
+                    problem,
+                    &parents,
+                    cost,
+                    expanded,
+                    frontier_peak,
+                )));
+            }
+            expanded += 1;
+
+            let node = &problem.get_nodes()[index];
+            for next in &node.neighbours {
+                let next_node = &problem.get_nodes()[next.index()];
+                if closed[next.index()]
+                    || next_node.value.cost().is_none()
+                    || !graph.has_line_of_sight(
+                        problem.get_nodes(),
+                        node.position,
+                        next_node.position,
+                    )
+                {
+                    continue;
+                }
+                // skips the expanded node if its parent sees the neighbour directly
+                let (parent, next_cost) = match parents[index] {
+                    Some(parent)
+                        if graph.has_line_of_sight(
+                            problem.get_nodes(),
+                            problem.get_position(parent),
+                            next_node.position,
+                        ) =>
+                    {
+                        (
+                            parent,
+                            costs[parent.index()]
+                                + problem
+                                    .get_position(parent)
+                                    .euclidean_distance(&next_node.position),
+                        )
+                    }
+                    _ => (
+                        id,
+                        cost + node.position.euclidean_distance(&next_node.position),
+                    ),
+                };
+                if next_cost >= costs[next.index()] {
+                    continue;
+                }
+                costs[next.index()] = next_cost;
+                parents[next.index()] = Some(parent);
+                frontier.push(State {
+                    estimate: next_cost + heuristic(*next),
+                    cost: next_cost,
+                    index: next.index(),
+                });
+                frontier_peak = frontier_peak.max(frontier.len());
+            }
+        }
+        Ok(None)
+    }
+
+    fn get_result(
+        problem: &Problem,
+        parents: &[Option<NodeId>],
+        length: f64,
+        expanded: usize,
+        frontier_peak: usize,
+    ) -> WaypointResult {
+        let mut waypoints = vec![problem.get_position(problem.goal)];
+        let mut next = parents[problem.goal.index()];
+        while let Some(parent) = next {
+            waypoints.push(problem.get_position(parent));
+            next = parents[parent.index()];
+        }
+        waypoints.reverse();
+        WaypointResult {
+            waypoints,
+            length,
+            expanded,
+            frontier_peak,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::a_star_search::AStarSearch;
+    use crate::connectivity::Connectivity;
+    use crate::position::Position;
+
+    fn map() -> &'static str {
+        "
+S.........
+..........
+.######...
+......#...
+......#..G
+"
+    }
+
+    #[test]
+    fn search_returns_waypoints_in_line_of_sight() {
+        let ascii_map = Graph::from_ascii(map()).unwrap();
+        let problem = ascii_map.get_path_problem().unwrap();
+        let result = ThetaStarSearch::search(&ascii_map.graph, &problem)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(Position::new(0, 0), result.waypoints[0]);
+        assert_eq!(
+            Position::new(4, 9),
+            result.waypoints[result.waypoints.len() - 1]
+        );
+        assert!(result.waypoints.len() > 2);
+        assert!(result
+            .waypoints
+            .windows(2)
+            .all(|line| ascii_map
+                .graph
+                .has_line_of_sight(problem.get_nodes(), line[0], line[1])));
+        let length: f64 = result
+            .waypoints
+            .windows(2)
+            .map(|line| line[0].euclidean_distance(&line[1]))
+            .sum();
+        assert!((length - result.length).abs() < 1e-9);
+    }
+
+    #[test]
+    fn search_returns_shorter_path_than_a_star_search() {
+        for connectivity in [
+            Connectivity::Four,
+            Connectivity::Eight,
+            Connectivity::EightWithoutCornerCutting,
+        ] {
+            let mut ascii_map = Graph::from_ascii(map()).unwrap();
+            ascii_map.graph = ascii_map.graph.with_connectivity(connectivity);
+            let graph = &ascii_map.graph;
+            let problem = ascii_map.get_path_problem().unwrap();
+            let result = ThetaStarSearch::search(graph, &problem).unwrap().unwrap();
+            // the A* search on Eight cuts blocked corners, which no line of sight passes
+            let a_star_connectivity = match connectivity {
+                Connectivity::Eight => Connectivity::EightWithoutCornerCutting,
+                connectivity => connectivity,
+            };
+            let a_star_graph = graph.clone().with_connectivity(a_star_connectivity);
+            let a_star_problem = Problem::new(
+                a_star_graph.get_path_nodes(),
+                problem.start,
+                problem.goal,
+                a_star_graph.size,
+            )
+            .unwrap();
+            let a_star_result = AStarSearch::search(&a_star_problem).unwrap().unwrap();
+
+            assert!(result.length < a_star_result.cost);
+            assert!(result.length >= Position::new(0, 0).euclidean_distance(&Position::new(4, 9)));
+            assert!(result
+                .waypoints
+                .windows(2)
+                .all(|line| graph.has_line_of_sight(problem.get_nodes(), line[0], line[1])));
+        }
+    }
+
+    #[test]
+    fn search_with_eight_does_not_cut_blocked_corner() {
+        let mut ascii_map = Graph::from_ascii("S#\n#G\n").unwrap();
+        ascii_map.graph = ascii_map.graph.with_connectivity(Connectivity::Eight);
+        let problem = ascii_map.get_path_problem().unwrap();
+
+        assert_eq!(
+            None,
+            ThetaStarSearch::search(&ascii_map.graph, &problem).unwrap()
+        );
+    }
+
+    #[test]
+    fn search_on_open_area_returns_straight_line() {
+        let graph = Graph::new(vec![0; 100], 10, 10).unwrap();
+        let problem = Problem::new(
+            graph.get_possible_nodes(),
+            graph.get_node_id_at_position(Position::new(9, 0)),
+            graph.get_node_id_at_position(Position::new(2, 6)),
+            graph.size,
+        )
+        .unwrap();
+        let result = ThetaStarSearch::search(&graph, &problem).unwrap().unwrap();
+
+        assert_eq!(
+            vec![Position::new(9, 0), Position::new(2, 6)],
+            result.waypoints
+        );
+        assert_eq!(85.0_f64.sqrt(), result.length);
+    }
+
+    #[test]
+    fn search_without_path_returns_none() {
+        let ascii_map = Graph::from_ascii("S.\n##\n.G").unwrap();
+        let problem = ascii_map.get_path_problem().unwrap();
+
+        assert_eq!(
+            None,
+            ThetaStarSearch::search(&ascii_map.graph, &problem).unwrap()
+        );
+    }
+}
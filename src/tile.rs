@@ -31,6 +31,59 @@ impl Tile {
             _ => Tile::None,
         }
     }
+
+    /// Builds a type from an ascii map character,
+    /// `.` for path, `#` for ground and ` ` for empty tile
+    ///
+    /// # Arguments
+    ///
+    /// * `character` - A char which defines the type
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::rust_problem_search::tile::Tile;
+    ///
+    /// assert_eq!(Some(Tile::Path), Tile::from_char('.'));
+    /// assert_eq!(None, Tile::from_char('x'));
+    /// ```
+    pub fn from_char(character: char) -> Option<Self> {
+        match character {
+            '.' => Some(Tile::Path),
+            '#' => Some(Tile::Ground),
+            ' ' => Some(Tile::None),
+            _ => None,
+        }
+    }
+
+    /// Returns the ascii map character of this type
+    pub fn to_char(&self) -> char {
+        match self {
+            Tile::None => ' ',
+            Tile::Path => '.',
+            Tile::Ground => '#',
+        }
+    }
+
+    /// Returns the cost to traverse a tile of this type,
+    /// or None if the tile is never walkable
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::rust_problem_search::tile::Tile;
+    ///
+    /// assert_eq!(Some(1), Tile::Path.cost());
+    /// assert_eq!(Some(3), Tile::None.cost());
+    /// assert_eq!(None, Tile::Ground.cost());
+    /// ```
+    pub fn cost(&self) -> Option<u32> {
+        match self {
+            Tile::None => Some(3),
+            Tile::Path => Some(1),
+            Tile::Ground => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -56,4 +109,26 @@ mod tests {
     fn from_with_u8_9_returns_none() {
         assert_eq!(Tile::None, Tile::from_u8(9));
     }
+
+    #[test]
+    fn from_char_returns_tile() {
+        assert_eq!(Some(Tile::Path), Tile::from_char('.'));
+        assert_eq!(Some(Tile::Ground), Tile::from_char('#'));
+        assert_eq!(Some(Tile::None), Tile::from_char(' '));
+        assert_eq!(None, Tile::from_char('x'));
+    }
+
+    #[test]
+    fn to_char_returns_character() {
+        assert_eq!('.', Tile::Path.to_char());
+        assert_eq!('#', Tile::Ground.to_char());
+        assert_eq!(' ', Tile::None.to_char());
+    }
+
+    #[test]
+    fn cost_returns_traversal_cost() {
+        assert_eq!(Some(1), Tile::Path.cost());
+        assert_eq!(Some(3), Tile::None.cost());
+        assert_eq!(None, Tile::Ground.cost());
+    }
 }
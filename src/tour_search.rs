@@ -0,0 +1,344 @@
+use crate::a_star_search::AStarSearch;
+use crate::graph::Graph;
+use crate::node::Node;
+use crate::position::Position;
+use crate::problem::Problem;
+use crate::search_error::SearchError;
+use crate::search_result::{RouteResult, SearchResult, TourResult};
+
+/// The largest number of targets whose order is searched exactly
+const EXACT_TARGETS: usize = 12;
+
+#[derive(Debug, Clone)]
+pub struct TourSearch {}
+
+impl TourSearch {
+    /// Returns the cheapest order to visit the targets from the start and the path through them,
+    /// or an error if a position is invalid or a target has no path from the start
+    ///
+    /// The costs between each pair of the start and the targets are found with the A* search.
+    /// Up to 12 targets the order is searched exactly with dynamic programming over the sets
+    /// of visited targets, more targets are ordered by visiting the nearest target next
+    /// and improved by reversing parts of the order as long as the tour gets cheaper.
+    /// The tour ends at the last target.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` The graph the nodes are build from
+    /// * `nodes` The nodes where to find the path, e.g. `graph.get_path_nodes()`
+    /// * `start` The position where the tour starts
+    /// * `targets` The positions to visit in any order
+    ///
+    /// # Example
+    /// ```
+    /// use rust_problem_search::graph::Graph;
+    /// use rust_problem_search::position::Position;
+    /// use rust_problem_search::tour_search::TourSearch;
+    ///
+    /// let graph = Graph::new(vec![1; 5], 5, 1).unwrap();
+    /// let targets = vec![Position::new(0, 4), Position::new(0, 1), Position::new(0, 2)];
+    ///
+    /// let start = Position::new(0, 0);
+    ///
+    /// let result = TourSearch::search(&graph, graph.get_path_nodes(), start, &targets).unwrap();
+    ///
+    /// assert_eq!(
+    ///     vec![Position::new(0, 1), Position::new(0, 2), Position::new(0, 4)],
+    ///     result.order
+    /// );
+    /// assert_eq!(4.0, result.route.cost);
+    /// ```
+    pub fn search(
+        graph: &Graph,
+        nodes: Vec<Node>,
+        start: Position,
+        targets: &[Position],
+    ) -> Result<TourResult, SearchError> {
+        if nodes.len() != graph.size {
+            return Err(SearchError::InvalidTileCount {
+                expected: graph.size,
+                actual: nodes.len(),
+            });
+        }
+        let mut waypoints = vec![start];
+        waypoints.extend_from_slice(targets);
+        if let Some(position) = waypoints
+            .iter()
+            .find(|position| !graph.contains(**position))
+        {
+            return Err(SearchError::PositionOutOfBounds(*position));
+        }
+
+        let start_id = graph.get_node_id_at_position(start);
+        let mut problem = Problem::new(nodes, start_id, start_id, graph.size)?;
+        let mut legs = Vec::with_capacity(waypoints.len());
+        for from in &waypoints {
+            let mut row = Vec::with_capacity(waypoints.len());
+            for to in &waypoints {
+                problem.start = graph.get_node_id_at_position(*from);
+                problem.goal = graph.get_node_id_at_position(*to);
+                row.push(
+                    AStarSearch::search(&problem)?.ok_or(SearchError::UnreachableTarget(*to))?,
+                );
+            }
+            legs.push(row);
+        }
+        let costs: Vec<Vec<f64>> = legs
+            .iter()
+            .map(|row| row.iter().map(|result| result.cost).collect())
+            .collect();
+
+        let optimal = targets.len() <= EXACT_TARGETS;
+        let order = if optimal {
+            TourSearch::get_exact_order(&costs)
+        } else {
+            let mut order = TourSearch::get_nearest_order(&costs);
+            TourSearch::improve_order(&costs, &mut order);
+            order
+        };
+        Ok(TourResult {
+            order: order.iter().map(|index| waypoints[*index]).collect(),
+            route: TourSearch::get_route(&legs, &order),
+            optimal,
+        })
+    }
+
+    /// Returns the indices of the targets in the cheapest order,
+    /// the cheapest cost for each set of visited targets and last target is kept
+    fn get_exact_order(costs: &[Vec<f64>]) -> Vec<usize> {
+        let count = costs.len() - 1;
+        if count == 0 {
+            return vec![];
+        }
+        let sets = 1 << count;
+        let mut best = vec![f64::INFINITY; sets * count];
+        let mut parents = vec![None; sets * count];
+        for last in 0..count {
+            best[(1 << last) * count + last] = costs[0][last + 1];
+        }
+        for set in 1..sets {
+            for last in (0..count).filter(|last| set & (1 << last) != 0) {
+                let cost = best[set * count + last];
+                for next in (0..count).filter(|next| set & (1 << next) == 0) {
+                    let next_index = (set | (1 << next)) * count + next;
+                    let next_cost = cost + costs[last + 1][next + 1];
+                    if next_cost < best[next_index] {
+                        best[next_index] = next_cost;
+                        parents[next_index] = Some(last);
+                    }
+                }
+            }
+        }
+
+        let mut set = sets - 1;
+        let mut last = (0..count)
+            .min_by(|a, b| best[set * count + a].total_cmp(&best[set * count + b]))
+            .unwrap_or(0);
+        let mut order = vec![last + 1];
+        while let Some(parent) = parents[set * count + last] {
+            set &= !(1 << last);
+            last = parent;
+            order.push(last + 1);
+        }
+        order.reverse();
+        order
+    }
+
+    /// Returns the indices of the targets, each one the nearest to the one before
+    fn get_nearest_order(costs: &[Vec<f64>]) -> Vec<usize> {
+        let mut visited = vec![false; costs.len()];
+        let mut order = Vec::with_capacity(costs.len() - 1);
+        let mut current = 0;
+        for _ in 1..costs.len() {
+            let next = (1..costs.len())
+                .filter(|index| !visited[*index])
+                .min_by(|a, b| costs[current][*a].total_cmp(&costs[current][*b]))
+                .unwrap_or(current);
+            visited[next] = true;
+            order.push(next);
+            current = next;
+        }
+        order
+    }
+
+    /// Reverses parts of the order while this makes the tour cheaper (2-opt)
+    fn improve_order(costs: &[Vec<f64>], order: &mut [usize]) {
+        let mut cost = TourSearch::get_cost(costs, order);
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for first in 0..order.len() {
+                for last in first + 1..order.len() {
+                    order[first..=last].reverse();
+                    let next_cost = TourSearch::get_cost(costs, order);
+                    if next_cost < cost {
+                        cost = next_cost;
+                        improved = true;
+                    } else {
+                        order[first..=last].reverse();
+                    }
+                }
+            }
+        }
+    }
+
+    fn get_cost(costs: &[Vec<f64>], order: &[usize]) -> f64 {
+        let mut previous = 0;
+        order
+            .iter()
+            .map(|index| {
+                let cost = costs[previous][*index];
+                previous = *index;
+                cost
+            })
+            .sum()
+    }
+
+    fn get_route(legs: &[Vec<SearchResult>], order: &[usize]) -> RouteResult {
+        let mut route = RouteResult {
+            path: legs[0][0].path.clone(),
+            length: 0,
+            cost: 0.0,
+            leg_costs: Vec::with_capacity(order.len()),
+            expanded: 0,
+            frontier_peak: 0,
+        };
+        let mut previous = 0;
+        for index in order {
+            let leg = &legs[previous][*index];
+            route.path.extend(leg.path.iter().skip(1));
+            route.cost += leg.cost;
+            route.leg_costs.push(leg.cost);
+            route.expanded += leg.expanded;
+            route.frontier_peak = route.frontier_peak.max(leg.frontier_peak);
+            previous = *index;
+        }
+        route.length = route.path.len() - 1;
+        route
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map() -> Graph {
+        let map = "
+..........
+.####.###.
+.#   .#  .
+.####.####
+..........
+";
+        Graph::from_ascii(map).unwrap().graph
+    }
+
+    fn permutations(values: &[usize]) -> Vec<Vec<usize>> {
+        if values.is_empty() {
+            return vec![vec![]];
+        }
+        let mut result = vec![];
+        for (i, value) in values.iter().enumerate() {
+            let mut rest = values.to_vec();
+            rest.remove(i);
+            for mut permutation in permutations(&rest) {
+                permutation.insert(0, *value);
+                result.push(permutation);
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn search_returns_cheapest_order() {
+        let graph = map();
+        let start = Position::new(0, 5);
+        let targets = vec![
+            Position::new(4, 0),
+            Position::new(2, 9),
+            Position::new(0, 0),
+            Position::new(4, 9),
+            Position::new(2, 4),
+        ];
+        let result =
+            TourSearch::search(&graph, graph.get_possible_nodes(), start, &targets).unwrap();
+
+        let mut problem = Problem::new(
+            graph.get_possible_nodes(),
+            graph.get_node_id_at_position(start),
+            graph.get_node_id_at_position(start),
+            graph.size,
+        )
+        .unwrap();
+        let mut cheapest = f64::INFINITY;
+        for permutation in permutations(&[0, 1, 2, 3, 4]) {
+            let mut cost = 0.0;
+            let mut previous = start;
+            for index in permutation {
+                problem.start = graph.get_node_id_at_position(previous);
+                problem.goal = graph.get_node_id_at_position(targets[index]);
+                cost += AStarSearch::search(&problem).unwrap().unwrap().cost;
+                previous = targets[index];
+            }
+            cheapest = cheapest.min(cost);
+        }
+        assert!(result.optimal);
+        assert_eq!(cheapest, result.route.cost);
+        assert_eq!(5, result.order.len());
+        assert!(targets.iter().all(|target| result.order.contains(target)));
+        assert_eq!(start, result.route.path[0]);
+        assert_eq!(result.order[4], result.route.path[result.route.length]);
+    }
+
+    #[test]
+    fn search_with_many_targets_returns_approximate_order() {
+        let graph = Graph::new(vec![1; 15 * 15], 15, 15).unwrap();
+        let targets: Vec<Position> = (0..15)
+            .map(|i| Position::new((i * 7) % 15, (i * 11) % 15))
+            .collect();
+        let start = Position::new(7, 7);
+        let result = TourSearch::search(&graph, graph.get_path_nodes(), start, &targets).unwrap();
+
+        let mut given_cost = 0.0;
+        let mut previous = start;
+        for target in &targets {
+            given_cost += previous.distance(target) as f64;
+            previous = *target;
+        }
+        assert!(!result.optimal);
+        assert_eq!(15, result.order.len());
+        assert!(targets.iter().all(|target| result.order.contains(target)));
+        assert!(result.route.cost < given_cost);
+        assert_eq!(
+            result.route.leg_costs.iter().sum::<f64>(),
+            result.route.cost
+        );
+    }
+
+    #[test]
+    fn search_without_targets_returns_start() {
+        let graph = map();
+        let result =
+            TourSearch::search(&graph, graph.get_path_nodes(), Position::new(0, 0), &[]).unwrap();
+
+        assert!(result.order.is_empty());
+        assert_eq!(vec![Position::new(0, 0)], result.route.path);
+        assert_eq!(0.0, result.route.cost);
+    }
+
+    #[test]
+    fn search_with_unreachable_target_returns_error() {
+        let graph = Graph::from_ascii("...\n###\n...").unwrap().graph;
+        let targets = vec![Position::new(0, 2), Position::new(2, 1)];
+
+        assert_eq!(
+            Err(SearchError::UnreachableTarget(Position::new(2, 1))),
+            TourSearch::search(
+                &graph,
+                graph.get_path_nodes(),
+                Position::new(0, 0),
+                &targets
+            )
+        );
+    }
+}
@@ -0,0 +1,257 @@
+use crate::a_star_search::AStarSearch;
+use crate::problem::Problem;
+use crate::search_error::SearchError;
+use crate::search_problem::SearchProblem;
+use crate::search_result::SearchResult;
+
+#[derive(Debug, Clone)]
+pub struct UniformCostSearch {}
+
+impl UniformCostSearch {
+    /// Returns an Option with the cheapest path and the search statistics,
+    /// or an error if the problem is invalid
+    ///
+    /// Entering a node costs the traversal cost of its tile,
+    /// the returned path has the lowest total cost instead of the fewest steps.
+    ///
+    /// # Arguments
+    ///
+    /// * `problem` The problem contains the start, the end and the nodes where to find the path
+    ///
+    /// # Example
+    /// ```
+    /// use rust_problem_search::uniform_cost_search::UniformCostSearch;
+    /// use rust_problem_search::problem::Problem;
+    /// use rust_problem_search::graph::Graph;
+    /// use rust_problem_search::position::Position;
+    ///
+    /// let tiles: Vec<u8> = vec![
+    ///     0, 0, 0, 0,
+    ///     1, 0, 0, 1,
+    ///     1, 1, 1, 1];
+    ///
+    /// let graph = Graph::new(tiles, 4, 3).unwrap();
+    ///
+    /// let problem = Problem::new(
+    ///     graph.get_possible_nodes(),
+    ///     graph.get_node_id_at_position(Position::new(1, 0)),
+    ///     graph.get_node_id_at_position(Position::new(1, 3)),
+    ///     graph.size,
+    /// ).unwrap();
+    ///
+    /// let result = UniformCostSearch::search(&problem).unwrap().unwrap();
+    ///
+    /// assert_eq!(Position::new(2, 3), result.path[4]);
+    /// assert_eq!(5.0, result.cost);
+    /// ```
+    pub fn search(problem: &Problem) -> Result<Option<SearchResult>, SearchError> {
+        problem.validate()?;
+        Ok(UniformCostSearch::search_problem(problem)
+            .map(|result| result.map(|id| problem.get_position(id))))
+    }
+
+    /// Returns an Option with the cheapest path of states to a goal and the search statistics,
+    /// the heuristic of the problem is ignored
+    ///
+    /// # Arguments
+    ///
+    /// * `problem` Any problem with an initial state, a goal test and successors
+    pub fn search_problem<P: SearchProblem>(problem: &P) -> Option<SearchResult<P::State>> {
+        AStarSearch::search_with_estimate(problem, |_, cost| cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::SQRT_2;
+
+    use crate::breath_first_search::BreathFirstSearch;
+    use crate::connectivity::Connectivity;
+    use crate::graph::Graph;
+    use crate::position::Position;
+
+    use super::*;
+
+    #[test]
+    fn search_with_valid_path_returns_result() {
+        let tiles: Vec<u8> = vec![
+            2, 1, 2,
+            2, 1, 2,
+            2, 1, 2];
+        let graph = Graph::new(tiles, 3, 3).unwrap();
+        let problem = Problem::new(
+            graph.get_path_nodes(),
+            graph.get_node_id_at_position(Position::new(0, 1)),
+            graph.get_node_id_at_position(Position::new(2, 1)),
+            graph.size,
+        )
+        .unwrap();
+        let result = UniformCostSearch::search(&problem).unwrap();
+        let unwrap_result = result.unwrap();
+
+        assert_eq!(
+            vec![
+                Position::new(0, 1),
+                Position::new(1, 1),
+                Position::new(2, 1),
+            ],
+            unwrap_result.path
+        );
+        assert_eq!(2.0, unwrap_result.cost);
+    }
+
+    #[test]
+    fn search_with_start_equals_goal_returns_result() {
+        let tiles: Vec<u8> = vec![
+            2, 1, 2,
+            2, 1, 2,
+            2, 1, 2];
+        let graph = Graph::new(tiles, 3, 3).unwrap();
+        let problem = Problem::new(
+            graph.get_path_nodes(),
+            graph.get_node_id_at_position(Position::new(0, 1)),
+            graph.get_node_id_at_position(Position::new(0, 1)),
+            graph.size,
+        )
+        .unwrap();
+        let result = UniformCostSearch::search(&problem).unwrap();
+        let unwrap_result = result.unwrap();
+
+        assert_eq!(vec![Position::new(0, 1)], unwrap_result.path);
+        assert_eq!(0.0, unwrap_result.cost);
+    }
+
+    #[test]
+    fn search_with_possible_path_prefers_existing_path() {
+        let tiles: Vec<u8> = vec![
+            2, 2, 2, 2, 2,
+            1, 0, 0, 0, 1,
+            1, 2, 2, 2, 1,
+            1, 1, 1, 1, 1,
+            2, 2, 2, 2, 2,
+        ];
+        let graph = Graph::new(tiles, 5, 5).unwrap();
+        let problem = Problem::new(
+            graph.get_possible_nodes(),
+            graph.get_node_id_at_position(Position::new(1, 0)),
+            graph.get_node_id_at_position(Position::new(1, 4)),
+            graph.size,
+        )
+        .unwrap();
+        let uniform_cost_result = UniformCostSearch::search(&problem).unwrap().unwrap();
+        let breath_first_result = BreathFirstSearch::search(&problem).unwrap().unwrap();
+
+        assert_eq!(
+            vec![
+                0, 0, 0, 0, 0,
+                1, 0, 0, 0, 1,
+                1, 0, 0, 0, 1,
+                1, 1, 1, 1, 1,
+                0, 0, 0, 0, 0,
+            ],
+            BreathFirstSearch::get_path(&uniform_cost_result, &graph)
+        );
+        assert_eq!(
+            vec![
+                0, 0, 0, 0, 0,
+                1, 1, 1, 1, 1,
+                0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0,
+            ],
+            BreathFirstSearch::get_path(&breath_first_result, &graph)
+        );
+        assert_eq!(8.0, uniform_cost_result.cost);
+        assert_eq!(10.0, breath_first_result.cost);
+    }
+
+    #[test]
+    fn search_with_possible_path_crosses_empty_tiles_when_cheaper() {
+        let tiles: Vec<u8> = vec![
+            2, 2, 2, 2, 2,
+            1, 0, 1, 2, 1,
+            1, 2, 1, 2, 1,
+            1, 2, 1, 2, 1,
+            1, 1, 1, 1, 1,
+        ];
+        let graph = Graph::new(tiles, 5, 5).unwrap();
+        let problem = Problem::new(
+            graph.get_possible_nodes(),
+            graph.get_node_id_at_position(Position::new(1, 0)),
+            graph.get_node_id_at_position(Position::new(1, 2)),
+            graph.size,
+        )
+        .unwrap();
+        let result = UniformCostSearch::search(&problem).unwrap().unwrap();
+
+        assert_eq!(
+            vec![
+                Position::new(1, 0),
+                Position::new(1, 1),
+                Position::new(1, 2),
+            ],
+            result.path
+        );
+        assert_eq!(4.0, result.cost);
+    }
+
+    #[test]
+    fn search_without_corner_cutting_walks_around_corners() {
+        let tiles: Vec<u8> = vec![
+            1, 2, 1,
+            1, 1, 1,
+            2, 2, 2];
+        let graph = Graph::new(tiles, 3, 3)
+            .unwrap()
+            .with_connectivity(Connectivity::EightWithoutCornerCutting);
+        let problem = Problem::new(
+            graph.get_path_nodes(),
+            graph.get_node_id_at_position(Position::new(0, 0)),
+            graph.get_node_id_at_position(Position::new(0, 2)),
+            graph.size,
+        )
+        .unwrap();
+        let result = UniformCostSearch::search(&problem).unwrap().unwrap();
+
+        assert_eq!(
+            vec![
+                Position::new(0, 0),
+                Position::new(1, 0),
+                Position::new(1, 1),
+                Position::new(1, 2),
+                Position::new(0, 2),
+            ],
+            result.path
+        );
+        assert_eq!(4.0, result.cost);
+    }
+
+    #[test]
+    fn search_with_eight_connectivity_cuts_corners() {
+        let tiles: Vec<u8> = vec![
+            1, 2, 1,
+            1, 1, 1,
+            2, 2, 2];
+        let graph = Graph::new(tiles, 3, 3)
+            .unwrap()
+            .with_connectivity(Connectivity::Eight);
+        let problem = Problem::new(
+            graph.get_path_nodes(),
+            graph.get_node_id_at_position(Position::new(0, 0)),
+            graph.get_node_id_at_position(Position::new(0, 2)),
+            graph.size,
+        )
+        .unwrap();
+        let result = UniformCostSearch::search(&problem).unwrap().unwrap();
+
+        assert_eq!(
+            vec![
+                Position::new(0, 0),
+                Position::new(1, 1),
+                Position::new(0, 2),
+            ],
+            result.path
+        );
+        assert!((2.0 * SQRT_2 - result.cost).abs() < 1e-9);
+    }
+}
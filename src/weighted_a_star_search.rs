@@ -0,0 +1,161 @@
+use crate::a_star_search::AStarSearch;
+use crate::problem::Problem;
+use crate::search_error::SearchError;
+use crate::search_problem::SearchProblem;
+use crate::search_result::BoundedSearchResult;
+
+#[derive(Debug, Clone)]
+pub struct WeightedAStarSearch {}
+
+impl WeightedAStarSearch {
+    /// Returns an Option with a path which costs at most `weight` times the cheapest path
+    /// together with this bound and the search statistics,
+    /// or an error if the problem or the weight is invalid
+    ///
+    /// The nodes are expanded by the estimate `cost + weight * heuristic`, with the heuristic
+    /// of `AStarSearch`. A weight above one expands fewer nodes for a worse path,
+    /// a weight of one is the A* search.
+    ///
+    /// # Arguments
+    ///
+    /// * `problem` The problem contains the start, the end and the nodes where to find the path
+    /// * `weight` A finite factor >= 0 for the heuristic
+    ///
+    /// # Example
+    /// ```
+    /// use rust_problem_search::graph::Graph;
+    /// use rust_problem_search::weighted_a_star_search::WeightedAStarSearch;
+    ///
+    /// let ascii_map = Graph::from_ascii("S...\n.##.\n...G\n").unwrap();
+    /// let problem = ascii_map.get_path_problem().unwrap();
+    ///
+    /// let bounded_result = WeightedAStarSearch::search(&problem, 2.0).unwrap().unwrap();
+    ///
+    /// assert_eq!(2.0, bounded_result.bound);
+    /// assert!(bounded_result.result.cost <= 2.0 * 5.0);
+    /// ```
+    pub fn search(
+        problem: &Problem,
+        weight: f64,
+    ) -> Result<Option<BoundedSearchResult>, SearchError> {
+        problem.validate()?;
+        let bound = WeightedAStarSearch::get_bound(weight)?;
+
+        let heuristic = AStarSearch::get_heuristic(problem);
+        Ok(
+            AStarSearch::search_with_estimate(problem, |id, cost| cost + weight * heuristic(id))
+                .map(|result| BoundedSearchResult {
+                    result: result.map(|id| problem.get_position(id)),
+                    bound,
+                }),
+        )
+    }
+
+    /// Returns an Option with a path of states which costs at most `weight` times
+    /// the cheapest path together with this bound and the search statistics,
+    /// or an error if the weight is invalid
+    ///
+    /// # Arguments
+    ///
+    /// * `problem` Any problem with an initial state, a goal test, successors and a heuristic
+    /// * `weight` A finite factor >= 0 for the heuristic
+    pub fn search_problem<P: SearchProblem>(
+        problem: &P,
+        weight: f64,
+    ) -> Result<Option<BoundedSearchResult<P::State>>, SearchError> {
+        let bound = WeightedAStarSearch::get_bound(weight)?;
+        Ok(AStarSearch::search_with_estimate(problem, |state, cost| {
+            cost + weight * problem.heuristic(state)
+        })
+        .map(|result| BoundedSearchResult { result, bound }))
+    }
+
+    /// Returns the suboptimality bound of a weight, a weight below one still finds the cheapest path
+    fn get_bound(weight: f64) -> Result<f64, SearchError> {
+        if weight.is_finite() && weight >= 0.0 {
+            Ok(weight.max(1.0))
+        } else {
+            Err(SearchError::InvalidWeight(weight))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Graph;
+    use crate::node::NodeId;
+
+    fn map() -> Problem {
+        let map = "
+...........
+.....#.....
+.....#.....
+.S...#...G.
+.....#.....
+...........
+";
+        Graph::from_ascii(map).unwrap().get_path_problem().unwrap()
+    }
+
+    #[test]
+    fn search_with_weight_one_returns_a_star_result() {
+        let bounded_result = WeightedAStarSearch::search(&map(), 1.0).unwrap().unwrap();
+
+        assert_eq!(1.0, bounded_result.bound);
+        assert_eq!(
+            AStarSearch::search(&map()).unwrap().unwrap(),
+            bounded_result.result
+        );
+    }
+
+    #[test]
+    fn search_with_higher_weight_expands_fewer_nodes() {
+        let a_star_result = AStarSearch::search(&map()).unwrap().unwrap();
+        let bounded_result = WeightedAStarSearch::search(&map(), 2.0).unwrap().unwrap();
+
+        assert_eq!(12.0, a_star_result.cost);
+        assert_eq!(12.0, bounded_result.result.cost);
+        assert!(bounded_result.result.expanded < a_star_result.expanded);
+    }
+
+    #[test]
+    fn search_returns_path_within_bound() {
+        let ascii_map = Graph::from_ascii("S   G\n.....\n").unwrap();
+        let problem = ascii_map.get_possible_problem().unwrap();
+        let a_star_result = AStarSearch::search(&problem).unwrap().unwrap();
+        let bounded_result = WeightedAStarSearch::search(&problem, 2.0).unwrap().unwrap();
+
+        assert_eq!(6.0, a_star_result.cost);
+        assert_eq!(10.0, bounded_result.result.cost);
+        assert!(bounded_result.result.cost <= bounded_result.bound * a_star_result.cost);
+    }
+
+    #[test]
+    fn search_with_weight_below_one_returns_bound_one() {
+        let bounded_result = WeightedAStarSearch::search(&map(), 0.5).unwrap().unwrap();
+
+        assert_eq!(1.0, bounded_result.bound);
+        assert_eq!(12.0, bounded_result.result.cost);
+    }
+
+    #[test]
+    fn search_with_invalid_weight_returns_error() {
+        assert_eq!(
+            Some(SearchError::InvalidWeight(-1.0)),
+            WeightedAStarSearch::search(&map(), -1.0).err()
+        );
+        assert!(WeightedAStarSearch::search(&map(), f64::NAN).is_err());
+    }
+
+    #[test]
+    fn search_with_unknown_goal_returns_error() {
+        let mut problem = map();
+        problem.goal = NodeId(99);
+
+        assert_eq!(
+            Some(SearchError::UnknownGoal(NodeId(99))),
+            WeightedAStarSearch::search(&problem, 2.0).err()
+        );
+    }
+}